@@ -0,0 +1,170 @@
+//! Derive macros for [`bevy_atomic_save`](https://github.com/Zeenobit/bevy_atomic_save).
+//!
+//! This crate should not be used directly. Instead, use the macros re-exported by `bevy_atomic_save`.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, parse_quote, Data, DeriveInput, Error, Field, Fields, Ident, Index, Member,
+    Path, Result, Type,
+};
+
+/// Derives `FromLoaded` for a struct or an enum.
+///
+/// The generated implementation calls `FromLoaded::try_from_loaded` on every field, in order, and returns
+/// all dangling entities of all fields.
+/// Each field may be configured using the `#[from_loaded(...)]` attribute:
+/// - `#[from_loaded(skip)]` leaves the field untouched.
+/// - `#[from_loaded(with = "path::to::function")]` calls `function(&mut field, loaded)` instead.
+/// - `#[from_loaded(try_with = "path::to::function")]` calls `function(&mut field, loaded)` instead, which must
+///   return `Result<(), DanglingEntities>`.
+#[proc_macro_derive(FromLoaded, attributes(from_loaded))]
+pub fn derive_from_loaded(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_loaded(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+/// How a single field is updated from `Loaded`.
+enum FieldMode {
+    Default,
+    Skip,
+    With(Path),
+    TryWith(Path),
+}
+
+fn field_mode(field: &Field) -> Result<FieldMode> {
+    let mut mode = FieldMode::Default;
+    for attr in &field.attrs {
+        if !attr.path().is_ident("from_loaded") {
+            continue;
+        }
+        attr.parse_nested_meta(|meta| {
+            if !matches!(mode, FieldMode::Default) {
+                return Err(meta.error("conflicting `from_loaded` attributes"));
+            }
+            if meta.path.is_ident("skip") {
+                mode = FieldMode::Skip;
+                Ok(())
+            } else if meta.path.is_ident("with") {
+                let function: syn::LitStr = meta.value()?.parse()?;
+                mode = FieldMode::With(function.parse()?);
+                Ok(())
+            } else if meta.path.is_ident("try_with") {
+                let function: syn::LitStr = meta.value()?.parse()?;
+                mode = FieldMode::TryWith(function.parse()?);
+                Ok(())
+            } else {
+                Err(meta.error("expected `skip`, `with = \"...\"` or `try_with = \"...\"`"))
+            }
+        })?;
+    }
+    Ok(mode)
+}
+
+/// Returns the statements which update the field bound to `binding`, which must be a `&mut` reference.
+///
+/// The result of each fallible update is pushed into `__results`.
+fn field_statements(
+    field: &Field,
+    binding: TokenStream2,
+    bounds: &mut Vec<Type>,
+) -> Result<TokenStream2> {
+    Ok(match field_mode(field)? {
+        FieldMode::Default => {
+            bounds.push(field.ty.clone());
+            quote! {
+                __results.push(::bevy_atomic_save::FromLoaded::try_from_loaded(#binding, loaded));
+            }
+        }
+        FieldMode::Skip => quote! {},
+        FieldMode::With(function) => quote! { #function(#binding, loaded); },
+        FieldMode::TryWith(function) => quote! { __results.push(#function(#binding, loaded)); },
+    })
+}
+
+/// Returns the identifiers used to bind the given fields in a pattern.
+fn field_bindings(fields: &Fields) -> Vec<Ident> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => format_ident!("__{}", ident),
+            None => format_ident!("__{}", index),
+        })
+        .collect()
+}
+
+fn field_members(fields: &Fields) -> Vec<Member> {
+    fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| match &field.ident {
+            Some(ident) => Member::Named(ident.clone()),
+            None => Member::Unnamed(Index::from(index)),
+        })
+        .collect()
+}
+
+fn expand_from_loaded(input: DeriveInput) -> Result<TokenStream2> {
+    let mut bounds = Vec::new();
+    let try_from_loaded = match &input.data {
+        Data::Struct(data) => {
+            let mut statements = Vec::new();
+            for (field, member) in data.fields.iter().zip(field_members(&data.fields)) {
+                let binding = quote! { &mut self.#member };
+                statements.push(field_statements(field, binding, &mut bounds)?);
+            }
+            quote! { #(#statements)* }
+        }
+        Data::Enum(data) => {
+            let mut arms = Vec::new();
+            for variant in &data.variants {
+                let ident = &variant.ident;
+                let members = field_members(&variant.fields);
+                let bindings = field_bindings(&variant.fields);
+                let mut statements = Vec::new();
+                for (field, binding) in variant.fields.iter().zip(&bindings) {
+                    statements.push(field_statements(field, quote! { #binding }, &mut bounds)?);
+                }
+                let pattern = quote! { Self::#ident { #(#members: ref mut #bindings,)* } };
+                arms.push(quote! { #pattern => { #(#statements)* } });
+            }
+            quote! { match *self { #(#arms)* } }
+        }
+        Data::Union(data) => {
+            return Err(Error::new_spanned(
+                data.union_token,
+                "`FromLoaded` cannot be derived for unions",
+            ));
+        }
+    };
+
+    let ident = &input.ident;
+    let mut generics = input.generics.clone();
+    if !generics.params.is_empty() {
+        let where_clause = generics.make_where_clause();
+        for ty in bounds {
+            where_clause
+                .predicates
+                .push(parse_quote! { #ty: ::bevy_atomic_save::FromLoaded });
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::bevy_atomic_save::FromLoaded for #ident #ty_generics #where_clause {
+            #[allow(unused_mut, unused_variables)]
+            fn try_from_loaded(
+                &mut self,
+                loaded: &::bevy_atomic_save::Loaded,
+            ) -> ::std::result::Result<(), ::bevy_atomic_save::DanglingEntities> {
+                let mut __results = ::std::vec::Vec::new();
+                #try_from_loaded
+                ::bevy_atomic_save::DanglingEntities::collect(__results)
+            }
+        }
+    })
+}
@@ -75,7 +75,8 @@ fn main() {
 fn app() -> App {
     let mut app = App::new();
     // Minimum required plugins:
-    app.add_plugins(MinimalPlugins).add_plugin(SavePlugin);
+    app.add_plugins(MinimalPlugins)
+        .add_plugin(SavePlugin::default());
 
     // Register all saved types:
     app.register_type::<Pawn>()
@@ -89,9 +90,6 @@ fn app() -> App {
         .add_system(spawn_pawn_sprites)
         .add_system(update_model_position);
 
-    // Register components which reference entities as "loaded".
-    app.register_loaded::<CurrentWeapon>();
-
     app
 }
 
@@ -103,8 +101,10 @@ struct Pawn;
 #[reflect(Component)]
 struct Position(Vec2);
 
-#[derive(Component, Default, Reflect)]
-#[reflect(Component)]
+// Any saved component which references entities should implement `FromLoaded`, which calls `try_from_loaded`
+// recursively on all of its members. Reflecting `FromLoaded` updates the component automatically during load.
+#[derive(Component, Default, Reflect, FromLoaded)]
+#[reflect(Component, FromLoaded)]
 struct CurrentWeapon(Option<Entity>);
 
 impl CurrentWeapon {
@@ -113,14 +113,6 @@ impl CurrentWeapon {
     }
 }
 
-// Any saved component which references entities should implement `FromLoaded`
-// by calling `from_loaded` recursively on all of its members which also implement `FromLoaded`.
-impl FromLoaded for CurrentWeapon {
-    fn from_loaded(&mut self, loaded: &Loaded) {
-        self.0.from_loaded(loaded)
-    }
-}
-
 #[derive(Bundle, Default)]
 struct PawnBundle {
     // Pawn data:
@@ -0,0 +1,60 @@
+use bevy::utils::HashMap;
+
+use super::*;
+
+/// A [`Resource`] which contains all type name aliases registered using [`RegisterSaveAlias`].
+#[derive(Resource, Clone, Default, Debug)]
+pub struct SaveAliases(pub(crate) HashMap<String, String>);
+
+impl SaveAliases {
+    /// Returns the current type name of the given saved type name, if it is an alias.
+    pub fn get(&self, alias: &str) -> Option<&str> {
+        self.0.get(alias).map(String::as_str)
+    }
+
+    /// Returns the current type name of the given saved type name.
+    ///
+    /// If the given type name is not an alias, it is returned as is.
+    pub fn resolve<'a>(&'a self, type_name: &'a str) -> &'a str {
+        self.get(type_name).unwrap_or(type_name)
+    }
+}
+
+/// Extension trait used to register type name aliases with an [`App`].
+///
+/// # Usage
+///
+/// Saved components and resources are identified by their full type name. If a saved type is renamed or moved
+/// into another module, any existing save files which contain it fail to deserialize. To solve this, the previous
+/// type name may be registered as an alias of the current type name. During load, any saved type name which
+/// is an alias is resolved to its current type name before it is looked up in the type registry.
+///
+/// Saves always use the current type name.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{RegisterSaveAlias, SavePlugin};
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// struct Pawn;
+///
+/// App::new()
+///     .add_plugin(SavePlugin::default())
+///     .register_type::<Pawn>()
+///     .register_save_alias("game::old::Pawn", std::any::type_name::<Pawn>());
+/// ```
+pub trait RegisterSaveAlias {
+    /// Registers `alias` as a previous type name of the type with the given current `type_name`.
+    fn register_save_alias(self, alias: impl Into<String>, type_name: impl Into<String>) -> Self;
+}
+
+impl RegisterSaveAlias for &mut App {
+    fn register_save_alias(self, alias: impl Into<String>, type_name: impl Into<String>) -> Self {
+        self.world
+            .get_resource_or_insert_with(SaveAliases::default)
+            .0
+            .insert(alias.into(), type_name.into());
+        self
+    }
+}
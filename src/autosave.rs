@@ -0,0 +1,182 @@
+use std::time::Duration;
+
+use bevy::ecs::system::BoxedSystem;
+
+use super::*;
+
+/// A [`Plugin`] which saves the [`World`] periodically.
+///
+/// # Usage
+///
+/// This plugin requires [`SavePlugin`]. Every time the autosave interval elapses, a save [`Request`] is queued
+/// into the next file in a rolling list of autosave paths. Each autosave is processed the same as a manual save
+/// (see [`SaveWorld::save`]), so it sends the same events.
+///
+/// Autosaves may be suspended (i.e. during cutscenes or combat) using a run criteria. While the run criteria
+/// returns [`ShouldRun::No`], no autosave is made. Once it returns [`ShouldRun::Yes`] and the interval has elapsed,
+/// the world is saved immediately.
+///
+/// By default, if nothing saved in the world has changed since the last successful autosave, the autosave file
+/// is not written (see [`Request::skip_if_unchanged`]), and the next autosave uses the same path.
+///
+/// # Example
+/// ```
+/// # use std::time::Duration;
+/// # use bevy::ecs::schedule::ShouldRun;
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{AutosavePlugin, SavePlugin};
+/// #[derive(Resource, Default)]
+/// struct InCombat(bool);
+///
+/// fn not_in_combat(in_combat: Res<InCombat>) -> ShouldRun {
+///     if in_combat.0 { ShouldRun::No } else { ShouldRun::Yes }
+/// }
+///
+/// App::new()
+///     .add_plugin(SavePlugin::default())
+///     .init_resource::<InCombat>()
+///     .add_plugin(
+///         AutosavePlugin::new(Duration::from_secs(300), ["autosave_1.sav", "autosave_2.sav"])
+///             .with_run_criteria(not_in_combat),
+///     );
+/// ```
+pub struct AutosavePlugin {
+    interval: Duration,
+    paths: Vec<PathBuf>,
+    skip_unchanged: bool,
+    run_criteria: Option<Box<RunCriteriaFn>>,
+}
+
+type RunCriteriaFn = dyn Fn() -> BoxedSystem<(), ShouldRun> + Send + Sync;
+
+impl AutosavePlugin {
+    /// Creates a new [`AutosavePlugin`] which saves the world every `interval`, rolling over the given `paths`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `paths` is empty.
+    pub fn new(interval: Duration, paths: impl IntoIterator<Item = impl Into<PathBuf>>) -> Self {
+        let paths: Vec<PathBuf> = paths.into_iter().map(Into::into).collect();
+        assert!(!paths.is_empty(), "autosave requires at least one path");
+        Self {
+            interval,
+            paths,
+            skip_unchanged: true,
+            run_criteria: None,
+        }
+    }
+
+    /// Sets whether an autosave should be skipped if nothing saved in the world has changed since the last one.
+    pub fn with_skip_unchanged(mut self, skip_unchanged: bool) -> Self {
+        self.skip_unchanged = skip_unchanged;
+        self
+    }
+
+    /// Sets a run criteria which suspends autosaves while it returns [`ShouldRun::No`].
+    pub fn with_run_criteria<Params>(
+        mut self,
+        run_criteria: impl IntoSystem<(), ShouldRun, Params> + Clone + Send + Sync + 'static,
+    ) -> Self {
+        self.run_criteria = Some(Box::new(move || {
+            Box::new(IntoSystem::into_system(run_criteria.clone()))
+        }));
+        self
+    }
+}
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Autosave {
+            timer: Timer::new(self.interval, TimerMode::Once),
+            paths: self.paths.clone(),
+            next: 0,
+            skip_unchanged: self.skip_unchanged,
+            last_checksum: None,
+            pending: None,
+        })
+        .add_system_to_stage(CoreStage::PostUpdate, tick_autosave)
+        .add_system_to_stage(CoreStage::PostUpdate, update_autosave_checksum);
+        match &self.run_criteria {
+            Some(run_criteria) => {
+                app.add_system_to_stage(CoreStage::Last, autosave.with_run_criteria(run_criteria()))
+            }
+            None => app.add_system_to_stage(CoreStage::Last, autosave),
+        };
+    }
+}
+
+/// A [`Resource`] which contains the state of periodic autosaves. See [`AutosavePlugin`] for details.
+#[derive(Resource)]
+pub struct Autosave {
+    timer: Timer,
+    paths: Vec<PathBuf>,
+    next: usize,
+    skip_unchanged: bool,
+    last_checksum: Option<u32>,
+    pending: Option<(RequestId, usize)>,
+}
+
+impl Autosave {
+    /// Returns the timer until the next autosave.
+    pub fn timer(&self) -> &Timer {
+        &self.timer
+    }
+
+    /// Returns the path of the next autosave.
+    pub fn next_path(&self) -> &Path {
+        &self.paths[self.next]
+    }
+
+    /// Restarts the timer until the next autosave.
+    ///
+    /// This is useful after a manual save, or after a load.
+    pub fn reset(&mut self) {
+        self.timer.reset();
+    }
+}
+
+/// A [`System`] which advances the [`Autosave`] timer.
+pub fn tick_autosave(time: Res<Time>, mut autosave: ResMut<Autosave>) {
+    autosave.timer.tick(time.delta());
+}
+
+/// A [`System`] which queues an autosave [`Request`] if the [`Autosave`] interval has elapsed.
+pub fn autosave(world: &mut World) {
+    let mut autosave = world.resource_mut::<Autosave>();
+    if !autosave.timer.finished() {
+        return;
+    }
+    autosave.timer.reset();
+    let index = autosave.next;
+    let mut request = Request::save(autosave.paths[index].clone());
+    if let (true, Some(checksum)) = (autosave.skip_unchanged, autosave.last_checksum) {
+        request = request.skip_if_unchanged(checksum);
+    }
+    autosave.next = (index + 1) % autosave.paths.len();
+    let id = world.queue_request(request);
+    world.resource_mut::<Autosave>().pending = Some((id, index));
+}
+
+/// A [`System`] which updates the checksum of the last [`Autosave`] once it is saved successfully.
+///
+/// If the autosave fails, the checksum of the previous autosave is kept, so the next autosave is not skipped.
+/// If the autosave is skipped, the next autosave uses its path instead.
+pub fn update_autosave_checksum(
+    mut autosave: ResMut<Autosave>,
+    mut saved: EventReader<SavedEvent>,
+    mut failed: EventReader<SaveFailedEvent>,
+) {
+    let Some((id, index)) = autosave.pending else {
+        return;
+    };
+    if let Some(event) = saved.iter().find(|event| event.id == id) {
+        if event.skipped {
+            debug!("autosave skipped: nothing changed");
+            autosave.next = index;
+        }
+        autosave.last_checksum = Some(event.checksum);
+        autosave.pending = None;
+    } else if failed.iter().any(|event| event.id == id) {
+        autosave.pending = None;
+    }
+}
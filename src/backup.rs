@@ -0,0 +1,142 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::*;
+
+/// Defines how previous versions of a save file are kept when it is overwritten.
+///
+/// # Usage
+///
+/// The backup policy of all save requests may be configured using [`SaveSettings`]. Before each save replaces
+/// an existing file, a copy of the existing file is kept next to it according to this policy.
+///
+/// Backups may be loaded using [`LoadWorld::load_latest_valid`] if the primary save file is corrupted.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum BackupPolicy {
+    /// No backups are kept.
+    #[default]
+    None,
+    /// The last N copies are kept as `world.ron.1`, `world.ron.2`, and so on, where `world.ron.1` is the newest.
+    Numbered(usize),
+    /// The last N copies are kept as `world.ron.{timestamp}`, where `timestamp` is the time at which
+    /// the copy was made, in milliseconds since the UNIX epoch.
+    ///
+    /// If a backup with the same or a later timestamp already exists (e.g. after two saves within the same
+    /// millisecond), the timestamp following the newest existing backup is used instead.
+    Timestamped(usize),
+}
+
+impl BackupPolicy {
+    /// Returns the paths of all existing backups of the save file at `path`, ordered from newest to oldest.
+    pub fn backups(self, path: impl AsRef<Path>) -> Vec<PathBuf> {
+        let path = path.as_ref();
+        match self {
+            Self::None => Vec::new(),
+            Self::Numbered(count) => (1..=count)
+                .map(|index| backup_path(path, index))
+                .take_while(|backup| backup.is_file())
+                .collect(),
+            Self::Timestamped(_) => suffixed_backups(path)
+                .into_iter()
+                .map(|(_, backup)| backup)
+                .collect(),
+        }
+    }
+
+    /// Keeps a copy of the existing save file at `path`, if any, and removes any backups beyond this policy.
+    pub(crate) fn rotate(self, path: &Path) -> io::Result<()> {
+        if !path.is_file() {
+            return Ok(());
+        }
+        match self {
+            Self::None | Self::Numbered(0) | Self::Timestamped(0) => {}
+            Self::Numbered(count) => {
+                remove_if_exists(&backup_path(path, count))?;
+                for index in (1..count).rev() {
+                    let backup = backup_path(path, index);
+                    if backup.is_file() {
+                        fs::rename(&backup, backup_path(path, index + 1))?;
+                    }
+                }
+                fs::copy(path, backup_path(path, 1))?;
+            }
+            Self::Timestamped(count) => {
+                let now = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .unwrap_or_default()
+                    .as_millis();
+                let timestamp = match suffixed_backups(path).first() {
+                    Some((newest, _)) if *newest >= now => newest + 1,
+                    _ => now,
+                };
+                fs::copy(path, backup_path(path, timestamp))?;
+                for (_, backup) in suffixed_backups(path).into_iter().skip(count) {
+                    remove_if_exists(&backup)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the path of a backup of the save file at `path` with the given `suffix`.
+fn backup_path(path: &Path, suffix: impl fmt::Display) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{suffix}"));
+    path.with_file_name(file_name)
+}
+
+/// Moves all backups of the save file at `from` so they become backups of the save file at `to`.
+///
+/// Any existing backups of the save file at `to` are removed first.
+pub(crate) fn rename_backups(from: &Path, to: &Path) -> io::Result<()> {
+    remove_backups(to)?;
+    for (suffix, backup) in suffixed_backups(from) {
+        fs::rename(backup, backup_path(to, suffix))?;
+    }
+    Ok(())
+}
+
+/// Removes all backups of the save file at `path`.
+pub(crate) fn remove_backups(path: &Path) -> io::Result<()> {
+    for (_, backup) in suffixed_backups(path) {
+        remove_if_exists(&backup)?;
+    }
+    Ok(())
+}
+
+/// Returns all backups of the save file at `path` with a numeric suffix, ordered from the largest suffix to the
+/// smallest.
+///
+/// This includes both numbered and timestamped backups. For timestamped backups, this order is from newest to oldest.
+fn suffixed_backups(path: &Path) -> Vec<(u128, PathBuf)> {
+    let (Some(dir), Some(file_name)) = (path.parent(), path.file_name().and_then(|n| n.to_str()))
+    else {
+        return Vec::new();
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<(u128, PathBuf)> = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name();
+            let suffix = name.to_str()?.strip_prefix(file_name)?.strip_prefix('.')?;
+            let timestamp = suffix.parse().ok()?;
+            Some((timestamp, entry.path()))
+        })
+        .collect();
+    backups.sort_by_key(|(timestamp, _)| std::cmp::Reverse(*timestamp));
+    backups
+}
+
+fn remove_if_exists(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(why) if why.kind() != io::ErrorKind::NotFound => Err(why),
+        _ => Ok(()),
+    }
+}
@@ -0,0 +1,88 @@
+use std::borrow::Cow;
+
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// The compression applied to the contents of a save file.
+///
+/// # Usage
+///
+/// Save files are not compressed by default. Each compression algorithm is only available if its matching
+/// cargo feature is enabled:
+/// - [`Compression::Zstd`] requires the `zstd` feature.
+/// - [`Compression::Gzip`] requires the `gzip` feature.
+/// - [`Compression::Lz4`] requires the `lz4` feature.
+///
+/// The default compression of all save requests may be configured using [`SaveSettings`], or it may be
+/// selected for each [`Request::Save`]. The compression of each save file is stored in its [`SaveHeader`],
+/// so it is detected automatically during load.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Compression {
+    /// No compression.
+    #[default]
+    None,
+    /// [Zstandard](https://github.com/facebook/zstd)
+    Zstd,
+    /// [Gzip](https://www.gnu.org/software/gzip/)
+    Gzip,
+    /// [LZ4](https://github.com/lz4/lz4)
+    Lz4,
+}
+
+impl Compression {
+    /// Returns `true` if the cargo feature required by this compression is enabled.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::None => true,
+            Self::Zstd => cfg!(feature = "zstd"),
+            Self::Gzip => cfg!(feature = "gzip"),
+            Self::Lz4 => cfg!(feature = "lz4"),
+        }
+    }
+
+    /// Compresses the given `bytes`.
+    pub fn compress(self, bytes: Vec<u8>) -> Result<Vec<u8>, SaveError> {
+        match self {
+            Self::None => Ok(bytes),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => Ok(zstd::encode_all(bytes.as_slice(), 0)?),
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+                encoder.write_all(&bytes)?;
+                Ok(encoder.finish()?)
+            }
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => Ok(lz4_flex::compress_prepend_size(&bytes)),
+            #[allow(unreachable_patterns)]
+            _ => Err(SaveError::UnsupportedCompression(self)),
+        }
+    }
+
+    /// Decompresses the given `bytes`.
+    pub fn decompress(self, bytes: &[u8]) -> Result<Cow<'_, [u8]>, LoadError> {
+        match self {
+            Self::None => Ok(Cow::Borrowed(bytes)),
+            #[cfg(feature = "zstd")]
+            Self::Zstd => zstd::decode_all(bytes)
+                .map(Cow::Owned)
+                .map_err(|why| LoadError::Decompress(why.into())),
+            #[cfg(feature = "gzip")]
+            Self::Gzip => {
+                let mut decompressed = Vec::new();
+                flate2::read::GzDecoder::new(bytes)
+                    .read_to_end(&mut decompressed)
+                    .map_err(|why| LoadError::Decompress(why.into()))?;
+                Ok(Cow::Owned(decompressed))
+            }
+            #[cfg(feature = "lz4")]
+            Self::Lz4 => lz4_flex::decompress_size_prepended(bytes)
+                .map(Cow::Owned)
+                .map_err(|why| LoadError::Decompress(why.into())),
+            #[allow(unreachable_patterns)]
+            _ => Err(LoadError::UnsupportedCompression(self)),
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::any::TypeId;
+
+use bevy::reflect::FromType;
+use bevy::utils::HashSet;
+
+use super::*;
+
+/// A filter which defines which components of saved entities are saved.
+///
+/// # Usage
+///
+/// By default, all reflected components of a saved [`Entity`] are saved, which may include transient data
+/// (e.g. cached pathfinding results) which does not need to be persisted. A [`SaveFilter`] may be used to
+/// exclude such components using a deny-list, or to save only specific components using an allow-list:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{SaveFilter, SavePlugin};
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// struct PathCache(Vec<Vec2>);
+///
+/// App::new().add_plugin(SavePlugin::default().with_filter(SaveFilter::default().deny::<PathCache>()));
+/// ```
+///
+/// Filters may be set globally using [`SavePlugin::with_filter`], per save group using
+/// [`SavePlugin::with_group_filter`], and per request using [`SaveWorld::save_with_filter`].
+/// A component is only saved if it is allowed by all applicable filters.
+///
+/// Components which should never be saved may be marked with [`ReflectSaveSkip`] instead.
+///
+/// Filters only apply to components. Saved resources are configured using [`RegisterSavedResource`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SaveFilter {
+    allow: Option<HashSet<TypeId>>,
+    deny: HashSet<TypeId>,
+}
+
+impl SaveFilter {
+    /// Adds the component `T` to the allow-list of this filter.
+    ///
+    /// If the allow-list is not empty, only components in it are saved.
+    pub fn allow<T: 'static>(self) -> Self {
+        self.allow_type_id(TypeId::of::<T>())
+    }
+
+    /// Adds the component `T` to the deny-list of this filter.
+    ///
+    /// Components in the deny-list are never saved, even if they are also in the allow-list.
+    pub fn deny<T: 'static>(self) -> Self {
+        self.deny_type_id(TypeId::of::<T>())
+    }
+
+    /// Adds the component with the given [`TypeId`] to the allow-list of this filter.
+    pub fn allow_type_id(mut self, type_id: TypeId) -> Self {
+        self.allow
+            .get_or_insert_with(HashSet::default)
+            .insert(type_id);
+        self
+    }
+
+    /// Adds the component with the given [`TypeId`] to the deny-list of this filter.
+    pub fn deny_type_id(mut self, type_id: TypeId) -> Self {
+        self.deny.insert(type_id);
+        self
+    }
+
+    /// Returns `true` if the component with the given [`TypeId`] should be saved.
+    pub fn is_allowed(&self, type_id: TypeId) -> bool {
+        !self.deny.contains(&type_id)
+            && match &self.allow {
+                Some(allow) => allow.contains(&type_id),
+                None => true,
+            }
+    }
+
+    /// Returns a filter which only allows components which are allowed by both this filter and `other`.
+    pub fn and(mut self, other: &SaveFilter) -> Self {
+        self.allow = match (self.allow, &other.allow) {
+            (Some(allow), Some(other)) => Some(allow.intersection(other).copied().collect()),
+            (Some(allow), None) => Some(allow),
+            (None, other) => other.clone(),
+        };
+        self.deny.extend(other.deny.iter().copied());
+        self
+    }
+}
+
+/// Type data which indicates that a reflected component should never be saved, regardless of any [`SaveFilter`].
+///
+/// # Usage
+///
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::*;
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component, SaveSkip)]
+/// struct PathCache(Vec<Vec2>);
+///
+/// # let mut app = App::new();
+/// app.register_type::<PathCache>();
+/// ```
+///
+/// Components defined in other crates may be marked using [`App::register_type_data`].
+#[derive(Clone)]
+pub struct ReflectSaveSkip;
+
+impl<T: Component> FromType<T> for ReflectSaveSkip {
+    fn from_type() -> Self {
+        Self
+    }
+}
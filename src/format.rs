@@ -0,0 +1,255 @@
+use bevy::reflect::{TypeRegistryArc, TypeRegistryInternal as TypeRegistry};
+use bevy::scene::serialize_ron;
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Serialize};
+
+#[cfg(feature = "bincode")]
+use bincode::Options;
+
+use super::*;
+
+/// Prefix of the header line at the start of every save file.
+///
+/// The header is a RON comment, so save files in [`SaveFormat::Ron`] remain valid RON.
+const HEADER_MAGIC: &[u8] = b"// bevy_atomic_save ";
+
+/// Length of the checksum after [`HEADER_MAGIC`], written as 8 hexadecimal digits followed by a space.
+const CHECKSUM_LEN: usize = 9;
+
+/// Maximum number of bytes of [`HEADER_MAGIC`] which may be damaged before a file is assumed to have no header.
+const MAX_DAMAGED_MAGIC: usize = 2;
+
+/// The serialization format of a save file.
+///
+/// # Usage
+///
+/// [`SaveFormat::Ron`] is the default format, and it is always available. It produces human readable
+/// save files, which is useful for dumps and diagnostics.
+///
+/// Binary formats are more compact and faster to save and load, but each of them is only available if
+/// its matching cargo feature is enabled:
+/// - [`SaveFormat::Bincode`] requires the `bincode` feature.
+/// - [`SaveFormat::Postcard`] requires the `postcard` feature.
+/// - [`SaveFormat::MessagePack`] requires the `rmp-serde` feature.
+///
+/// The format of each save file is stored in its [`SaveHeader`], so it is detected automatically during load.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SaveFormat {
+    /// [Rusty Object Notation](https://github.com/ron-rs/ron)
+    #[default]
+    Ron,
+    /// [Bincode](https://github.com/bincode-org/bincode)
+    Bincode,
+    /// [Postcard](https://github.com/jamesmunns/postcard)
+    Postcard,
+    /// [MessagePack](https://github.com/3Hren/msgpack-rust)
+    MessagePack,
+}
+
+impl SaveFormat {
+    /// Returns `true` if the cargo feature required by this format is enabled.
+    pub fn is_supported(self) -> bool {
+        match self {
+            Self::Ron => true,
+            Self::Bincode => cfg!(feature = "bincode"),
+            Self::Postcard => cfg!(feature = "postcard"),
+            Self::MessagePack => cfg!(feature = "rmp-serde"),
+        }
+    }
+
+    /// Serializes the given [`SavedWorld`] using this format.
+    pub fn serialize(
+        self,
+        saved: &SavedWorld,
+        registry: &TypeRegistryArc,
+    ) -> Result<Vec<u8>, SaveError> {
+        let serializer = SavedWorldSerializer::new(saved, registry);
+        match self {
+            Self::Ron => Ok(serialize_ron(serializer)?.into_bytes()),
+            #[cfg(feature = "bincode")]
+            Self::Bincode => {
+                bincode::serialize(&serializer).map_err(|why| SaveError::Serialize(why.into()))
+            }
+            #[cfg(feature = "postcard")]
+            Self::Postcard => {
+                postcard::to_stdvec(&serializer).map_err(|why| SaveError::Serialize(why.into()))
+            }
+            #[cfg(feature = "rmp-serde")]
+            Self::MessagePack => {
+                rmp_serde::to_vec(&serializer).map_err(|why| SaveError::Serialize(why.into()))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(SaveError::UnsupportedFormat(self)),
+        }
+    }
+
+    /// Deserializes a [`SavedWorld`] from the given `bytes` using this format.
+    ///
+    /// Saved type names are resolved using the given [`SaveAliases`].
+    pub fn deserialize(
+        self,
+        bytes: &[u8],
+        registry: &TypeRegistry,
+        aliases: &SaveAliases,
+    ) -> Result<SavedWorld, LoadError> {
+        let deserializer = SavedWorldDeserializer {
+            type_registry: registry,
+            aliases,
+        };
+        match self {
+            Self::Ron => {
+                let mut ron_deserializer = ron::Deserializer::from_bytes(bytes)?;
+                deserializer
+                    .deserialize(&mut ron_deserializer)
+                    .map_err(|why| ron_deserializer.span_error(why).into())
+            }
+            #[cfg(feature = "bincode")]
+            Self::Bincode => {
+                let options = bincode::DefaultOptions::new()
+                    .with_fixint_encoding()
+                    .allow_trailing_bytes();
+                let mut bincode_deserializer = bincode::Deserializer::from_slice(bytes, options);
+                deserializer
+                    .deserialize(&mut bincode_deserializer)
+                    .map_err(|why| LoadError::Deserialize(why.into()))
+            }
+            #[cfg(feature = "postcard")]
+            Self::Postcard => {
+                let mut postcard_deserializer = postcard::Deserializer::from_bytes(bytes);
+                deserializer
+                    .deserialize(&mut postcard_deserializer)
+                    .map_err(|why| LoadError::Deserialize(why.into()))
+            }
+            #[cfg(feature = "rmp-serde")]
+            Self::MessagePack => {
+                let mut rmp_deserializer = rmp_serde::Deserializer::from_read_ref(bytes);
+                deserializer
+                    .deserialize(&mut rmp_deserializer)
+                    .map_err(|why| LoadError::Deserialize(why.into()))
+            }
+            #[allow(unreachable_patterns)]
+            _ => Err(LoadError::UnsupportedFormat(self)),
+        }
+    }
+}
+
+/// The header of a save file, which describes how the rest of the file is encoded.
+///
+/// The header is written as a single line at the start of each save file. Files without a header
+/// are assumed to be plain [`SaveFormat::Ron`] files.
+///
+/// The header line starts with a CRC32 checksum of the entire file (excluding the checksum itself), which is
+/// verified during load before the [`World`] is modified. This means any damage to either the header or the
+/// rest of the file is detected. See [`LoadError::Corrupted`] for details.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveHeader {
+    /// Serialization format of the save file.
+    #[serde(default)]
+    pub format: SaveFormat,
+    /// Compression of the save file.
+    #[serde(default)]
+    pub compression: Compression,
+    /// Metadata of the save file, which may be read without loading the rest of the file.
+    #[serde(default)]
+    pub metadata: SaveMetadata,
+    /// Schema version of the save file. See [`SaveMigrations`] for details.
+    #[serde(default)]
+    pub version: u32,
+}
+
+impl SaveHeader {
+    /// Returns this header as a single line, which should be written at the start of a save file followed by
+    /// the given `body` (i.e. the rest of the file).
+    ///
+    /// The returned line includes the checksum of itself and the given `body`.
+    pub fn to_bytes(&self, body: &[u8]) -> Result<Vec<u8>, SaveError> {
+        let mut line = b" ".to_vec();
+        line.extend_from_slice(ron::to_string(self)?.as_bytes());
+        line.push(b'\n');
+        let checksum = file_checksum(HEADER_MAGIC, &line, body);
+        let mut bytes = HEADER_MAGIC.to_vec();
+        bytes.extend_from_slice(format!("{checksum:08x}").as_bytes());
+        bytes.extend_from_slice(&line);
+        Ok(bytes)
+    }
+
+    /// Reads and verifies the header at the start of the given `bytes`, and returns it along with the rest of the
+    /// file.
+    ///
+    /// The checksum of the file is verified before the header is parsed.
+    pub fn read(bytes: &[u8]) -> Result<(Self, &[u8]), LoadError> {
+        let Some((magic, rest)) = split_magic(bytes) else {
+            return Ok((Self::default(), bytes));
+        };
+        if rest.len() < CHECKSUM_LEN {
+            return Err(LoadError::InvalidHeader);
+        }
+        let expected = std::str::from_utf8(&rest[..CHECKSUM_LEN - 1])
+            .ok()
+            .and_then(|digits| u32::from_str_radix(digits, 16).ok());
+        let found = file_checksum(magic, &rest[CHECKSUM_LEN - 1..], &[]);
+        if expected != Some(found) {
+            return Err(LoadError::Corrupted { expected, found });
+        }
+        Self::split_line(&rest[CHECKSUM_LEN..])
+    }
+
+    /// Parses the header line at the start of the given `bytes`, and returns it along with the rest of the file.
+    fn split_line(bytes: &[u8]) -> Result<(Self, &[u8]), LoadError> {
+        let Some(end) = bytes.iter().position(|&byte| byte == b'\n') else {
+            return Err(LoadError::InvalidHeader);
+        };
+        Ok((Self::parse(&bytes[..end])?, &bytes[end + 1..]))
+    }
+
+    fn parse(line: &[u8]) -> Result<Self, LoadError> {
+        ron::de::from_bytes(line).map_err(|_| LoadError::InvalidHeader)
+    }
+}
+
+/// Returns the CRC32 checksum of a save file, which consists of the given parts, excluding the checksum itself.
+fn file_checksum(magic: &[u8], line: &[u8], body: &[u8]) -> u32 {
+    let mut hasher = crc32fast::Hasher::new();
+    hasher.update(magic);
+    hasher.update(line);
+    hasher.update(body);
+    hasher.finalize()
+}
+
+/// Splits the given `bytes` into [`HEADER_MAGIC`] and the rest of the file, if the file has a header.
+///
+/// A file whose magic is slightly damaged is still assumed to have a header, so it fails verification
+/// instead of being loaded as a plain [`SaveFormat::Ron`] file.
+fn split_magic(bytes: &[u8]) -> Option<(&[u8], &[u8])> {
+    if bytes.len() < HEADER_MAGIC.len() {
+        return None;
+    }
+    let (magic, rest) = bytes.split_at(HEADER_MAGIC.len());
+    let damaged = magic
+        .iter()
+        .zip(HEADER_MAGIC)
+        .filter(|(byte, expected)| byte != expected)
+        .count();
+    (damaged <= MAX_DAMAGED_MAGIC).then_some((magic, rest))
+}
+
+/// Reads the [`SaveHeader`] of a save file at the given `path`, without reading the rest of the file.
+pub fn read_save_header(path: impl AsRef<Path>) -> Result<SaveHeader, LoadError> {
+    let mut reader = io::BufReader::new(File::open(path)?);
+    let mut magic = [0; HEADER_MAGIC.len()];
+    match reader.read_exact(&mut magic) {
+        Ok(()) if magic == HEADER_MAGIC => {}
+        Ok(()) => return Ok(SaveHeader::default()),
+        Err(why) if why.kind() == io::ErrorKind::UnexpectedEof => return Ok(SaveHeader::default()),
+        Err(why) => return Err(why.into()),
+    }
+    let mut line = Vec::new();
+    reader.read_until(b'\n', &mut line)?;
+    if line.pop() != Some(b'\n') {
+        return Err(LoadError::InvalidHeader);
+    }
+    if line.len() < CHECKSUM_LEN {
+        return Err(LoadError::InvalidHeader);
+    }
+    SaveHeader::parse(&line[CHECKSUM_LEN..])
+}
@@ -0,0 +1,153 @@
+use std::any::{type_name, TypeId};
+use std::marker::PhantomData;
+
+use bevy::ecs::world::EntityMut;
+
+use super::*;
+
+/// A [`Component`] which indicates that its [`Entity`] should be saved as part of the save group `G`.
+///
+/// # Usage
+///
+/// Save groups allow independent parts of a [`World`] (e.g. "player profile", "world state" and "settings")
+/// to be saved into and loaded from different files. The group `G` may be any type, and it is typically
+/// an empty marker type:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{LoadWorld, SaveGroup, SaveWorld};
+/// struct Profile;
+///
+/// fn save_profile(mut commands: Commands) {
+///     commands.spawn(SaveGroup::<Profile>::default());
+///     commands.save_group::<Profile>("profile.sav");
+/// }
+///
+/// fn load_profile(mut commands: Commands) {
+///     commands.load_group::<Profile>("profile.sav");
+/// }
+/// ```
+///
+/// A group save (see [`SaveWorld::save_group`]) only saves entities with a [`SaveGroup<G>`] component. A group load
+/// (see [`LoadWorld::load_group`]) only despawns entities with a [`SaveGroup<G>`] or [`UnloadGroup<G>`] component,
+/// and inserts [`SaveGroup<G>`] (instead of [`Save`]) into all loaded entities.
+///
+/// Saved resources (see [`RegisterSavedResource`]) are only saved and loaded by requests which are not grouped.
+///
+/// Once a group is saved or loaded for the first time, all of its entities are assigned a [`SaveId`], same as
+/// entities with a [`Save`] component.
+#[derive(Component)]
+pub struct SaveGroup<G>(PhantomData<fn() -> G>);
+
+/// A [`Component`] which indicates that its [`Entity`] and all of its [`Children`] should be despawned before
+/// the save group `G` is loaded. See [`SaveGroup`] for details.
+#[derive(Component)]
+pub struct UnloadGroup<G>(PhantomData<fn() -> G>);
+
+macro_rules! impl_group_marker {
+    ($name:ident) => {
+        impl<G> Default for $name<G> {
+            fn default() -> Self {
+                Self(PhantomData)
+            }
+        }
+
+        impl<G> Clone for $name<G> {
+            fn clone(&self) -> Self {
+                Self(PhantomData)
+            }
+        }
+
+        impl<G> fmt::Debug for $name<G> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}<{}>", stringify!($name), type_name::<G>())
+            }
+        }
+    };
+}
+
+impl_group_marker!(SaveGroup);
+impl_group_marker!(UnloadGroup);
+
+/// A type-erased save group, used to scope a [`Request`] to a [`SaveGroup`].
+#[derive(Clone, Copy)]
+pub struct SaveGroupFilter {
+    type_id: TypeId,
+    name: &'static str,
+    saved: fn(&mut World) -> Vec<Entity>,
+    unloaded: fn(&mut World) -> Vec<Entity>,
+    unassigned: fn(&mut World) -> Vec<Entity>,
+    insert: fn(&mut EntityMut),
+}
+
+impl SaveGroupFilter {
+    /// Returns the filter of the save group `G`.
+    pub fn of<G: 'static>() -> Self {
+        Self {
+            type_id: TypeId::of::<G>(),
+            name: type_name::<G>(),
+            saved: |world| {
+                world
+                    .query_filtered::<Entity, With<SaveGroup<G>>>()
+                    .iter(world)
+                    .collect()
+            },
+            unloaded: |world| {
+                world
+                    .query_filtered::<Entity, Or<(With<SaveGroup<G>>, With<UnloadGroup<G>>)>>()
+                    .iter(world)
+                    .collect()
+            },
+            unassigned: |world| {
+                world
+                    .query_filtered::<Entity, (With<SaveGroup<G>>, Without<SaveId>)>()
+                    .iter(world)
+                    .collect()
+            },
+            insert: |entity| {
+                entity.insert(SaveGroup::<G>::default());
+            },
+        }
+    }
+
+    /// Returns the [`TypeId`] of the save group.
+    pub fn type_id(&self) -> TypeId {
+        self.type_id
+    }
+
+    /// Returns the type name of the save group.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns all entities in the given [`World`] which should be saved as part of this group.
+    pub fn saved_entities(&self, world: &mut World) -> Vec<Entity> {
+        (self.saved)(world)
+    }
+
+    /// Returns all entities in the given [`World`] which should be despawned before this group is loaded.
+    pub fn unloaded_entities(&self, world: &mut World) -> Vec<Entity> {
+        (self.unloaded)(world)
+    }
+
+    /// Returns all entities in the given [`World`] in this group which do not have a [`SaveId`].
+    pub fn unassigned_entities(&self, world: &mut World) -> Vec<Entity> {
+        (self.unassigned)(world)
+    }
+
+    /// Inserts the [`SaveGroup`] component of this group into the given `entity`.
+    pub fn insert(&self, entity: &mut EntityMut) {
+        (self.insert)(entity);
+    }
+}
+
+impl fmt::Debug for SaveGroupFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("SaveGroupFilter").field(&self.name).finish()
+    }
+}
+
+impl PartialEq for SaveGroupFilter {
+    fn eq(&self, other: &Self) -> bool {
+        self.type_id == other.type_id
+    }
+}
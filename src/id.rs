@@ -0,0 +1,332 @@
+use bevy::reflect::ReflectMut;
+use bevy::utils::HashMap;
+
+use super::*;
+
+/// A [`Component`] which uniquely identifies a saved [`Entity`] across saves and loads.
+///
+/// # Usage
+///
+/// Unlike an [`Entity`], which may change every time a world is loaded, a [`SaveId`] is preserved through
+/// [`save_world`] and [`load_world`]. This makes it suitable for references which must remain valid across
+/// save files, such as quest targets, achievements, or networked objects.
+///
+/// A unique [`SaveId`] is assigned automatically to every entity with a [`Save`] component (or a [`SaveGroup`]
+/// component of a group which was saved or loaded) during [`CoreStage::PostUpdate`]. It may also be inserted manually, in which case it is the responsibility of
+/// the caller to keep it unique.
+///
+/// Use [`SaveIdIndex`] to find the current [`Entity`] of any [`SaveId`], and [`SaveRef`] to store references
+/// to entities by their [`SaveId`].
+#[derive(Component, Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+#[reflect(Component, PartialEq, Hash)]
+pub struct SaveId(u64);
+
+impl SaveId {
+    /// Returns the raw value of this ID.
+    ///
+    /// IDs are assigned in increasing order, starting from `1`. A raw value of `0` is never assigned.
+    pub fn get(self) -> u64 {
+        self.0
+    }
+}
+
+/// A reference to an [`Entity`] by its [`SaveId`].
+///
+/// # Usage
+///
+/// Components which store a [`SaveRef`] instead of an [`Entity`] do not need to implement [`FromLoaded`],
+/// since the referenced entity is resolved using the [`SaveIdIndex`] whenever it is needed:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{SaveIdIndex, SaveRef};
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// struct QuestTarget(SaveRef);
+///
+/// fn update_quests(query: Query<&QuestTarget>, index: Res<SaveIdIndex>) {
+///     for QuestTarget(target) in &query {
+///         if let Some(entity) = target.entity(&index) {
+///             /* ... */
+///         }
+///     }
+/// }
+/// ```
+#[derive(Reflect, FromReflect, Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct SaveRef(Option<SaveId>);
+
+impl SaveRef {
+    /// Returns a reference to the entity with the given [`SaveId`].
+    pub fn new(id: SaveId) -> Self {
+        Self(Some(id))
+    }
+
+    /// Returns the [`SaveId`] of the referenced entity, if any.
+    pub fn id(&self) -> Option<SaveId> {
+        self.0
+    }
+
+    /// Returns the current [`Entity`] of the referenced entity, if it exists.
+    pub fn entity(&self, index: &SaveIdIndex) -> Option<Entity> {
+        self.0.and_then(|id| index.entity(id))
+    }
+}
+
+impl From<SaveId> for SaveRef {
+    fn from(id: SaveId) -> Self {
+        Self::new(id)
+    }
+}
+
+/// A [`Resource`] which maps each [`SaveId`] to its current [`Entity`], and vice versa.
+///
+/// # Usage
+///
+/// This index is updated during [`CoreStage::PostUpdate`], and rebuilt whenever a world is loaded, so it
+/// is valid during [`SaveStage::PostLoad`].
+///
+/// This is also a saved resource (see [`RegisterSavedResource`]), which only saves the next [`SaveId`] to be
+/// assigned. This ensures the IDs of entities which were despawned before save are never reused after load.
+#[derive(Resource, Reflect, Default, Debug)]
+#[reflect(Resource)]
+pub struct SaveIdIndex {
+    #[reflect(ignore)]
+    entities: HashMap<SaveId, Entity>,
+    #[reflect(ignore)]
+    ids: HashMap<Entity, SaveId>,
+    next: u64,
+}
+
+impl SaveIdIndex {
+    /// Returns the current [`Entity`] with the given [`SaveId`], if any.
+    pub fn entity(&self, id: SaveId) -> Option<Entity> {
+        self.entities.get(&id).copied()
+    }
+
+    /// Returns the [`SaveId`] of the given [`Entity`], if any.
+    pub fn id(&self, entity: Entity) -> Option<SaveId> {
+        self.ids.get(&entity).copied()
+    }
+
+    /// Returns a [`SaveRef`] to the given [`Entity`], if it has a [`SaveId`].
+    pub fn save_ref(&self, entity: Entity) -> Option<SaveRef> {
+        self.id(entity).map(SaveRef::new)
+    }
+
+    /// Returns an iterator over all indexed IDs and their entities.
+    pub fn iter(&self) -> impl Iterator<Item = (SaveId, Entity)> + '_ {
+        self.entities.iter().map(|(&id, &entity)| (id, entity))
+    }
+
+    /// Returns the number of indexed entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entities are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn next_id(&mut self) -> SaveId {
+        self.next = self.next.max(1);
+        let id = SaveId(self.next);
+        self.next += 1;
+        id
+    }
+
+    fn insert(&mut self, id: SaveId, entity: Entity) {
+        self.remove(entity);
+        if let Some(previous) = self.entities.insert(id, entity) {
+            warn!("duplicate save ID {id:?}: {previous:?} and {entity:?}");
+            self.ids.remove(&previous);
+        }
+        self.ids.insert(entity, id);
+        self.next = self.next.max(id.0 + 1);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(id) = self.ids.remove(&entity) {
+            self.entities.remove(&id);
+        }
+    }
+}
+
+/// A [`System`] which assigns a [`SaveId`] to all entities with a [`Save`] component, and updates the
+/// [`SaveIdIndex`].
+pub(crate) fn update_save_ids(
+    mut commands: Commands,
+    mut index: ResMut<SaveIdIndex>,
+    unassigned: Query<Entity, (With<Save>, Without<SaveId>)>,
+    changed: Query<(Entity, &SaveId), Changed<SaveId>>,
+    removed: RemovedComponents<SaveId>,
+) {
+    for entity in removed.iter() {
+        index.remove(entity);
+    }
+    for (entity, &id) in &changed {
+        index.insert(id, entity);
+    }
+    for entity in &unassigned {
+        let id = index.next_id();
+        commands.entity(entity).insert(id);
+        index.insert(id, entity);
+    }
+}
+
+/// A [`Resource`] which contains all save groups which were saved or loaded, whose entities are assigned a [`SaveId`].
+#[derive(Resource, Default)]
+pub(crate) struct SaveIdGroups(Vec<SaveGroupFilter>);
+
+/// A [`System`] which assigns a [`SaveId`] to all entities in any save group which was saved or loaded.
+pub(crate) fn update_group_save_ids(world: &mut World) {
+    let Some(groups) = world.get_resource::<SaveIdGroups>() else {
+        return;
+    };
+    let groups = groups.0.clone();
+    for group in groups {
+        let unassigned = group.unassigned_entities(world);
+        assign_save_ids(world, unassigned);
+    }
+}
+
+/// Assigns a [`SaveId`] to all entities in the given save `group` which do not have one, and keeps assigning
+/// one to any new entities in it during [`CoreStage::PostUpdate`].
+pub(crate) fn index_group_save_ids(world: &mut World, group: SaveGroupFilter) {
+    let mut groups = world.get_resource_or_insert_with(SaveIdGroups::default);
+    if !groups.0.contains(&group) {
+        groups.0.push(group);
+    }
+    let unassigned = group.unassigned_entities(world);
+    assign_save_ids(world, unassigned);
+}
+
+/// Rebuilds the [`SaveIdIndex`] of the given [`World`], and assigns a [`SaveId`] to all entities with a [`Save`]
+/// component which do not have one.
+pub(crate) fn index_save_ids(world: &mut World) {
+    let mut index = world.remove_resource::<SaveIdIndex>().unwrap_or_default();
+    index.entities.clear();
+    index.ids.clear();
+    for (entity, &id) in world.query::<(Entity, &SaveId)>().iter(world) {
+        index.insert(id, entity);
+    }
+    world.insert_resource(index);
+    let unassigned: Vec<Entity> = world
+        .query_filtered::<Entity, (With<Save>, Without<SaveId>)>()
+        .iter(world)
+        .collect();
+    assign_save_ids(world, unassigned);
+}
+
+/// Assigns a new [`SaveId`] to each of the given `entities`, and adds them to the [`SaveIdIndex`].
+fn assign_save_ids(world: &mut World, entities: Vec<Entity>) {
+    if entities.is_empty() {
+        return;
+    }
+    let mut index = world.remove_resource::<SaveIdIndex>().unwrap_or_default();
+    for entity in entities {
+        let id = index.next_id();
+        world.entity_mut(entity).insert(id);
+        index.insert(id, entity);
+    }
+    world.insert_resource(index);
+}
+
+/// Adds the given loaded `entities` to the [`SaveIdIndex`] of the given [`World`].
+///
+/// Any loaded entity without a [`SaveId`], or with a [`SaveId`] which is already in use by another entity,
+/// is assigned a new one. Any [`SaveRef`] within the loaded entities which references a reassigned [`SaveId`]
+/// is updated to reference the new one.
+pub(crate) fn index_loaded_save_ids(world: &mut World, entities: Vec<Entity>) {
+    let mut index = world.remove_resource::<SaveIdIndex>().unwrap_or_default();
+    let mut reassigned = HashMap::new();
+    for &entity in &entities {
+        let id = match world.get::<SaveId>(entity) {
+            Some(&id) if index.entity(id).is_none() => id,
+            loaded_id => {
+                let loaded_id = loaded_id.copied();
+                let id = index.next_id();
+                if let Some(loaded_id) = loaded_id {
+                    debug!("save ID reassigned: {loaded_id:?} -> {id:?}");
+                    reassigned.insert(loaded_id, id);
+                }
+                world.entity_mut(entity).insert(id);
+                id
+            }
+        };
+        index.insert(id, entity);
+    }
+    world.insert_resource(index);
+    if !reassigned.is_empty() {
+        remap_save_refs(world, &entities, &reassigned);
+    }
+}
+
+/// Updates every [`SaveRef`] within the components of the given `entities` which references a reassigned [`SaveId`].
+fn remap_save_refs(world: &mut World, entities: &[Entity], reassigned: &HashMap<SaveId, SaveId>) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for reflect_component in registry
+        .iter()
+        .filter_map(|registration| registration.data::<ReflectComponent>())
+    {
+        for &entity in entities {
+            let Some(mut component) = reflect_component.reflect_mut(world, entity) else {
+                continue;
+            };
+            visit_save_refs(&mut *component, &mut |save_ref| {
+                if let Some(&id) = save_ref.0.and_then(|id| reassigned.get(&id)) {
+                    save_ref.0 = Some(id);
+                }
+            });
+        }
+    }
+}
+
+/// Calls `f` for every [`SaveRef`] within the given reflected value, recursively.
+fn visit_save_refs(value: &mut dyn Reflect, f: &mut impl FnMut(&mut SaveRef)) {
+    if let Some(save_ref) = value.downcast_mut::<SaveRef>() {
+        f(save_ref);
+        return;
+    }
+    match value.reflect_mut() {
+        ReflectMut::Struct(value) => {
+            for index in 0..value.field_len() {
+                visit_save_refs(value.field_at_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::TupleStruct(value) => {
+            for index in 0..value.field_len() {
+                visit_save_refs(value.field_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Tuple(value) => {
+            for index in 0..value.field_len() {
+                visit_save_refs(value.field_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::List(value) => {
+            for index in 0..value.len() {
+                visit_save_refs(value.get_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Array(value) => {
+            for index in 0..value.len() {
+                visit_save_refs(value.get_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Map(value) => {
+            let keys: Vec<Box<dyn Reflect>> = (0..value.len())
+                .filter_map(|index| value.get_at(index).map(|(key, _)| key.clone_value()))
+                .collect();
+            for key in keys {
+                visit_save_refs(value.get_mut(&*key).unwrap(), f);
+            }
+        }
+        ReflectMut::Enum(value) => {
+            for index in 0..value.field_len() {
+                visit_save_refs(value.field_at_mut(index).unwrap(), f);
+            }
+        }
+        ReflectMut::Value(_) => {}
+    }
+}
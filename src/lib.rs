@@ -1,17 +1,50 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::path::PathBuf;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, prelude::*};
+use std::path::{Path, PathBuf};
 
 use bevy::ecs::schedule::ShouldRun;
 use bevy::prelude::*;
-
+use bevy::tasks::{IoTaskPool, Task};
+use futures_lite::future;
+
+mod alias;
+mod autosave;
+mod backup;
+mod compression;
+mod filter;
+mod format;
+mod group;
+mod id;
 mod load;
+mod metadata;
+mod migration;
 mod plugin;
+mod request;
+mod resource;
 mod save;
-
+mod scene;
+mod slot;
+
+pub use alias::*;
+pub use autosave::*;
+pub use backup::*;
+pub use compression::*;
+pub use filter::*;
+pub use format::*;
+pub use group::*;
+pub use id::*;
 pub use load::*;
+pub use metadata::*;
+pub use migration::*;
 pub use plugin::*;
+pub use request::*;
+pub use resource::*;
 pub use save::*;
+pub use scene::*;
+pub use slot::*;
+
+pub use bevy_atomic_save_derive::FromLoaded;
 
 #[derive(StageLabel)]
 pub enum SaveStage {
@@ -27,16 +60,20 @@ pub enum SaveStage {
 
 /// Trait used to save a [`World`] to a file.
 pub trait SaveWorld {
-    /// Inserts a new [`Request::Save`] with the given `path` into this [`World`].
+    /// Queues a new [`Request::Save`] with the given `path` for this [`World`].
     ///
     /// This request is processed during [`SaveStage::Save`]. During this stage, any [`Entity`]
     /// with a [`Save`] [`Component`] is serialized into a [`DynamicScene`] which is then written
-    /// into a file located at given `path`.
+    /// into a file located at given `path`, along with any resources registered using [`RegisterSavedResource`].
     ///
-    /// If the save request fails, an [`error`] message will be logged with cause of failure.
-    fn save(self, path: impl Into<PathBuf>);
+    /// The file is written atomically (see [`write_atomic`]), so any previous file at `path` is only
+    /// replaced once the new one is fully written to disk.
+    ///
+    /// A [`SavedEvent`] is sent if the save request succeeds. Otherwise, a [`SaveFailedEvent`] is sent
+    /// and an [`error`] message will be logged with cause of failure.
+    fn save(self, path: impl Into<PathBuf>) -> RequestId;
 
-    /// Inserts a new [`Request::Save`] with the given `path` into this [`World`].
+    /// Queues a new [`Request::Save`] with the given `path` for this [`World`].
     ///
     /// This request is processed during [`SaveStage::Save`]. During this stage, all entities
     /// are serialized into a [`DynamicScene`] which is then written into a file located at given `path`.
@@ -48,52 +85,74 @@ pub trait SaveWorld {
     /// As a result, this function should be used primarily for diagnostics, as it can be useful for inspecting
     /// worlds in a very simple, and somewhat readable text format.
     ///
-    /// If the dump request fails, an [`error`] message will be logged with cause of failure.
-    fn dump(self, path: impl Into<PathBuf>);
+    /// A [`SavedEvent`] is sent if the dump request succeeds. Otherwise, a [`SaveFailedEvent`] is sent
+    /// and an [`error`] message will be logged with cause of failure.
+    fn dump(self, path: impl Into<PathBuf>) -> RequestId;
+
+    /// Queues a new [`Request::Save`] with the given `path` for this [`World`], which is saved in the background.
+    ///
+    /// This is the same as [`SaveWorld::save()`], except that only the extraction of saved entities and resources
+    /// happens during [`SaveStage::Save`]. Serialization and file IO happen on the [`IoTaskPool`](bevy::tasks::IoTaskPool)
+    /// to avoid stalling the frame. See [`PendingSaves`] for details.
+    fn save_in_background(self, path: impl Into<PathBuf>) -> RequestId;
+
+    /// Queues a new [`Request::Save`] with the given `path` for this [`World`], using the given [`SaveFormat`].
+    ///
+    /// This is the same as [`SaveWorld::save()`], except that the file is serialized using the given format instead
+    /// of [`SaveFormat::Ron`]. The format is detected automatically during load.
+    fn save_with_format(self, path: impl Into<PathBuf>, format: SaveFormat) -> RequestId;
+
+    /// Queues a new [`Request::Save`] with the given `path` for the save group `G` of this [`World`].
+    ///
+    /// This is the same as [`SaveWorld::save()`], except that only entities with a [`SaveGroup<G>`] component are
+    /// saved, and saved resources are not. See [`SaveGroup`] for details.
+    fn save_group<G: 'static>(self, path: impl Into<PathBuf>) -> RequestId;
+
+    /// Queues a new [`Request::Save`] with the given `path` for this [`World`], using the given [`SaveFilter`].
+    ///
+    /// This is the same as [`SaveWorld::save()`], except that only components allowed by the given filter (and
+    /// any filters in [`SaveSettings`]) are saved. See [`SaveFilter`] for details.
+    fn save_with_filter(self, path: impl Into<PathBuf>, filter: SaveFilter) -> RequestId;
 }
 
-impl SaveWorld for &mut Commands<'_, '_> {
-    fn save(self, path: impl Into<PathBuf>) {
-        self.insert_resource(Request::Save {
-            path: path.into(),
-            mode: SaveMode::Filtered,
-        })
+impl<T: QueueRequest> SaveWorld for T {
+    fn save(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::save(path))
     }
 
-    fn dump(self, path: impl Into<PathBuf>) {
-        self.insert_resource(Request::Save {
-            path: path.into(),
-            mode: SaveMode::Dump,
-        })
+    fn dump(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::dump(path))
+    }
+
+    fn save_in_background(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::save(path).in_background())
+    }
+
+    fn save_with_format(self, path: impl Into<PathBuf>, format: SaveFormat) -> RequestId {
+        self.queue_request(Request::save(path).with_format(format))
     }
-}
 
-impl SaveWorld for &mut World {
-    fn save(self, path: impl Into<PathBuf>) {
-        self.insert_resource(Request::Save {
-            path: path.into(),
-            mode: SaveMode::Filtered,
-        })
+    fn save_group<G: 'static>(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::save(path).with_group::<G>())
     }
 
-    fn dump(self, path: impl Into<PathBuf>) {
-        self.insert_resource(Request::Save {
-            path: path.into(),
-            mode: SaveMode::Dump,
-        })
+    fn save_with_filter(self, path: impl Into<PathBuf>, filter: SaveFilter) -> RequestId {
+        self.queue_request(Request::save(path).with_filter(filter))
     }
 }
 
 /// Trait used to load a [`World`] from a file.
 pub trait LoadWorld {
-    /// Inserts a new [`Request::Load`] from the given `path` for this [`World`].
+    /// Queues a new [`Request::Load`] from the given `path` for this [`World`].
     ///
     /// This request is processed during [`SaveStage::Load`]. During this stage, any [`Entity`]
     /// with a [`Save`] or [`Unload`] [`Component`] is despawned recursively. Then, entities are deserialized
     /// from the given path (which should point to a previously saved file) and spawned in this [`World`]
     /// with a new [`Loaded`] [`Component`]. This component is removed after [`SaveStage::PostLoad`].
     ///
-    /// If the load request fails, an [`error`] message will be logged with cause of failure.
+    /// A [`LoadedEvent`] is sent if the load request succeeds. Otherwise, a [`LoadFailedEvent`] is sent
+    /// and an [`error`] message will be logged with cause of failure.
+    /// If the load request fails, the world is left untouched and [`SaveStage::PostLoad`] is skipped.
     ///
     /// After a successful load, there is no guarantee that a loaded entity will have the same index with which
     /// it was saved. This is because there may already be an entity with that index in this world which is
@@ -102,41 +161,83 @@ pub trait LoadWorld {
     ///
     /// To solve this, during [`SaveStage::PostLoad`], systems may use the [`Loaded`] component to update entity
     /// references as required. See examples for how this would be done.
-    fn load(self, path: impl Into<PathBuf>);
+    fn load(self, path: impl Into<PathBuf>) -> RequestId;
+
+    /// Queues a new [`Request::Load`] from the given `path` for this [`World`], which is read in the background.
+    ///
+    /// This is the same as [`LoadWorld::load()`], except that the file is read and deserialized on the
+    /// [`IoTaskPool`](bevy::tasks::IoTaskPool) over multiple frames. The [`World`] is only unloaded and loaded
+    /// once the saved data is ready, during [`SaveStage::Load`]. Until then, [`LoadProgress`] may be used to
+    /// track the progress of the load.
+    fn load_in_background(self, path: impl Into<PathBuf>) -> RequestId;
+
+    /// Queues a new [`Request::Load`] from the given `path` for this [`World`], which falls back to backups.
+    ///
+    /// This is the same as [`LoadWorld::load()`], except that if the file at `path` can not be read, is corrupted,
+    /// or fails validation, its backups are tried instead, from newest to oldest, according to the [`BackupPolicy`]
+    /// in [`SaveSettings`]. The first valid file is loaded, and its path is reported in the [`LoadedEvent`].
+    ///
+    /// If no valid file is found, a [`LoadFailedEvent`] is sent with the cause of failure of the file at `path`.
+    fn load_latest_valid(self, path: impl Into<PathBuf>) -> RequestId;
+
+    /// Queues a new [`Request::Load`] from the given `path` for this [`World`], which keeps all existing entities.
+    ///
+    /// This is the same as [`LoadWorld::load()`], except that no entities are despawned before load, and saved
+    /// resources are not loaded. This is useful to stream in a saved level chunk, or to import a saved blueprint.
+    ///
+    /// Only the loaded entities are included in [`Loaded`], so only their entity references are updated during
+    /// [`SaveStage::PostLoad`]. Any loaded [`SaveId`] which is already in use is replaced with a new one, and any
+    /// [`SaveRef`] to it within the loaded entities is updated to match.
+    fn load_additive(self, path: impl Into<PathBuf>) -> RequestId;
+
+    /// Queues a new [`Request::Load`] from the given `path` for this [`World`], which keeps all existing entities
+    /// and inserts the given `marker` component into every loaded entity.
+    ///
+    /// See [`LoadWorld::load_additive`] for details.
+    fn load_additive_with_marker<M: Component + Clone>(
+        self,
+        path: impl Into<PathBuf>,
+        marker: M,
+    ) -> RequestId;
+
+    /// Queues a new [`Request::Load`] from the given `path` for the save group `G` of this [`World`].
+    ///
+    /// This is the same as [`LoadWorld::load()`], except that only entities with a [`SaveGroup<G>`] or
+    /// [`UnloadGroup<G>`] component are despawned, saved resources are not loaded, and all loaded entities
+    /// are marked with [`SaveGroup<G>`] instead of [`Save`]. See [`SaveGroup`] for details.
+    fn load_group<G: 'static>(self, path: impl Into<PathBuf>) -> RequestId;
 }
 
-impl LoadWorld for &mut Commands<'_, '_> {
-    fn load(self, path: impl Into<PathBuf>) {
-        self.insert_resource(Request::Load { path: path.into() })
+impl<T: QueueRequest> LoadWorld for T {
+    fn load(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::load(path))
     }
-}
 
-impl LoadWorld for &mut World {
-    fn load(self, path: impl Into<PathBuf>) {
-        self.insert_resource(Request::Load { path: path.into() })
+    fn load_in_background(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::load(path).in_background())
     }
-}
 
-pub enum SaveMode {
-    /// Save entities with a [`Save`] component.
-    Filtered,
-    /// Save all entities.
-    Dump,
-}
+    fn load_latest_valid(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::load(path).with_fallback())
+    }
 
-/// A [`Resource`] used to trigger a save or load request.
-#[derive(Resource)]
-pub enum Request {
-    Save { path: PathBuf, mode: SaveMode },
-    Load { path: PathBuf },
-}
+    fn load_additive(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::load(path).with_load_mode(LoadMode::Additive))
+    }
 
-impl Request {
-    fn should_save(&self) -> bool {
-        matches!(self, Self::Save { .. })
+    fn load_additive_with_marker<M: Component + Clone>(
+        self,
+        path: impl Into<PathBuf>,
+        marker: M,
+    ) -> RequestId {
+        self.queue_request(
+            Request::load(path)
+                .with_load_mode(LoadMode::Additive)
+                .with_marker(marker),
+        )
     }
 
-    fn should_load(&self) -> bool {
-        matches!(self, Self::Load { .. })
+    fn load_group<G: 'static>(self, path: impl Into<PathBuf>) -> RequestId {
+        self.queue_request(Request::load(path).with_group::<G>())
     }
 }
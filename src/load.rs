@@ -1,8 +1,15 @@
 use bevy::ecs::entity::EntityMap;
-use bevy::scene::serde::SceneDeserializer;
-use bevy::utils::HashMap;
-use ron::Deserializer;
-use serde::de::DeserializeSeed;
+use bevy::ecs::reflect::ReflectMapEntities;
+use bevy::ecs::reflect::ReflectResource;
+use bevy::reflect::{FromType, ReflectRef, TypeRegistryInternal as TypeRegistry};
+use bevy::scene::SceneSpawnError;
+use bevy::utils::{hashbrown, HashMap};
+use ron::error::SpannedError;
+use smallvec::SmallVec;
+use std::collections::VecDeque;
+use std::hash::{BuildHasher, Hash};
+use std::sync::atomic::{AtomicU64, AtomicU8, Ordering};
+use std::sync::Arc;
 
 use super::*;
 
@@ -24,87 +31,843 @@ pub struct Unload;
 ///
 /// To solve this, the [`Loaded`] resource may be used to update any entity references
 /// during [`SaveStage::PostLoad`]. This resource is added to world during this stage and
-/// it contains the previously saved index of the loaded entities. Any type which references entities
-/// can update its references using this resource.
+/// it contains the previously saved entities (i.e. their index and generation) of the loaded entities.
+/// Any type which references entities can update its references using this resource.
 ///
 /// This can be done more conveniently by implementing the [`FromLoaded`] trait for components which
 /// reference entities.
 ///
 /// [`Resource`]: bevy::prelude::Resource
-#[derive(Resource)]
-pub struct Loaded(HashMap<u32, Entity>);
+#[derive(Resource, Default)]
+pub struct Loaded {
+    entities: HashMap<Entity, Entity>,
+    saved_entities: HashMap<Entity, Entity>,
+}
 
 impl Loaded {
+    /// Returns the loaded entity of the given saved entity, if it was saved.
+    ///
+    /// Both the index and the generation of the saved entity must match. A saved reference to an entity which
+    /// was despawned before save is not valid, even if its index was reused by another saved entity.
     pub fn entity(&self, entity: Entity) -> Option<Entity> {
-        self.0.get(&entity.index()).copied()
+        self.entities.get(&entity).copied()
+    }
+
+    /// Returns the saved entity of the given loaded entity, if it was loaded.
+    pub fn saved_entity(&self, entity: Entity) -> Option<Entity> {
+        self.saved_entities.get(&entity).copied()
+    }
+
+    /// Returns an iterator over all saved entities and their loaded counterparts.
+    pub fn iter(&self) -> impl Iterator<Item = (Entity, Entity)> + '_ {
+        self.entities
+            .iter()
+            .map(|(&saved_entity, &entity)| (saved_entity, entity))
+    }
+
+    /// Returns the number of loaded entities.
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Returns `true` if no entities were loaded.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+
+    fn insert(&mut self, saved_entity: Entity, entity: Entity) {
+        self.entities.insert(saved_entity, entity);
+        self.saved_entities.insert(entity, saved_entity);
     }
 }
 
-/// A [`RunCriteria`] which returns [`ShouldRun::Yes`] if there is a load [`Request`] present; [`ShouldRun::No`] otherwise.
-pub fn should_load(request: Option<Res<Request>>) -> ShouldRun {
-    match request.map(|request| request.should_load()) {
+/// A [`RunCriteria`] which returns [`ShouldRun::Yes`] if there is a load [`Request`] queued, or a background
+/// load in progress; [`ShouldRun::No`] otherwise.
+pub fn should_load(
+    queue: Option<Res<RequestQueue>>,
+    pending_load: Option<Res<PendingLoad>>,
+) -> ShouldRun {
+    match queue.map(|queue| queue.has_load()) {
         Some(true) => ShouldRun::Yes,
+        _ if pending_load.is_some() => ShouldRun::Yes,
         _ => ShouldRun::No,
     }
 }
 
-/// A [`System`] which handles a load [`Request`] and starts the load process.
-pub fn load(world: &mut World) {
-    if let Request::Load { path } = world.resource::<Request>() {
-        match File::open(path) {
-            Ok(mut file) => {
-                let mut serialized_scene = Vec::new();
-                if let Err(why) = file.read_to_end(&mut serialized_scene) {
-                    error!("file read failed: {why:?}");
-                }
-                match Deserializer::from_bytes(&serialized_scene) {
-                    Ok(mut deserializer) => {
-                        let result = SceneDeserializer {
-                            type_registry: &world.resource::<AppTypeRegistry>().read(),
-                        }
-                        .deserialize(&mut deserializer);
-                        match result {
-                            Ok(scene) => {
-                                load_world(world, scene);
-                            }
-                            Err(why) => {
-                                error!("deserialization failed: {why:?}");
-                            }
-                        }
-                    }
-                    Err(why) => {
-                        error!("deserializer creation failed: {why:?}");
-                    }
-                }
+/// A [`RunCriteria`] which returns [`ShouldRun::Yes`] if the [`World`] has just been loaded; [`ShouldRun::No`] otherwise.
+///
+/// This is the run criteria of [`SaveStage::PostLoad`].
+pub fn should_post_load(loaded: Option<Res<Loaded>>) -> ShouldRun {
+    match loaded {
+        Some(_) => ShouldRun::Yes,
+        None => ShouldRun::No,
+    }
+}
+
+/// An [`Event`] sent after a load [`Request`] is completed successfully.
+///
+/// This event is sent during [`SaveStage::Load`], after all entities have been loaded.
+#[derive(Debug)]
+pub struct LoadedEvent {
+    /// ID of the load request.
+    pub id: RequestId,
+    /// Path of the loaded file.
+    ///
+    /// If the request was made using [`LoadWorld::load_latest_valid`], this may be the path of a backup.
+    pub path: PathBuf,
+    /// Mode of the load request.
+    pub mode: LoadMode,
+}
+
+/// An [`Event`] sent after a load [`Request`] fails.
+#[derive(Debug)]
+pub struct LoadFailedEvent {
+    /// ID of the load request.
+    pub id: RequestId,
+    /// Path of the file which failed to load.
+    pub path: PathBuf,
+    /// Mode of the load request.
+    pub mode: LoadMode,
+    /// Cause of failure.
+    pub error: LoadError,
+}
+
+/// Cause of a failed load [`Request`].
+#[derive(Debug)]
+pub enum LoadError {
+    /// The save file could not be read.
+    Io(io::Error),
+    /// The header of the save file is malformed. See [`SaveHeader`] for details.
+    InvalidHeader,
+    /// The contents of the save file (including its [`SaveHeader`]) do not match the checksum stored in it.
+    ///
+    /// This typically means the file was damaged on disk after it was saved. In this case, the [`World`] is
+    /// left untouched, and a backup save file may be loaded instead.
+    ///
+    /// The `expected` checksum is `None` if the stored checksum itself is damaged.
+    Corrupted { expected: Option<u32>, found: u32 },
+    /// The save file is saved in a [`SaveFormat`] which is not supported, because its cargo feature is not enabled.
+    UnsupportedFormat(SaveFormat),
+    /// The save file is compressed using a [`Compression`] which is not supported, because its cargo feature
+    /// is not enabled.
+    UnsupportedCompression(Compression),
+    /// The save file could not be decompressed.
+    Decompress(Box<dyn std::error::Error + Send + Sync>),
+    /// The save file could not be deserialized into a [`SavedWorld`].
+    Deserialize(Box<dyn std::error::Error + Send + Sync>),
+    /// The deserialized [`SavedWorld`] could not be written into the [`World`].
+    WorldWrite(SceneSpawnError),
+    /// The deserialized [`SavedWorld`] contains a resource which is not registered using [`RegisterSavedResource`].
+    UnregisteredResource { type_name: String },
+    /// The save file was saved with a schema version which can not be migrated to the current version.
+    /// See [`SaveMigrations`] for details.
+    UnsupportedVersion(u32),
+    /// A migration of the save file from the given schema version failed.
+    Migration {
+        version: u32,
+        error: Box<dyn std::error::Error + Send + Sync>,
+    },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Io(why) => write!(f, "file read failed: {why}"),
+            Self::InvalidHeader => write!(f, "invalid header"),
+            Self::Corrupted {
+                expected: Some(expected),
+                found,
+            } => write!(
+                f,
+                "corrupted file: checksum mismatch (expected {expected:08x}, found {found:08x})"
+            ),
+            Self::Corrupted {
+                expected: None,
+                found,
+            } => write!(f, "corrupted file: invalid checksum (found {found:08x})"),
+            Self::UnsupportedFormat(format) => write!(f, "unsupported format: {format:?}"),
+            Self::UnsupportedCompression(compression) => {
+                write!(f, "unsupported compression: {compression:?}")
+            }
+            Self::Decompress(why) => write!(f, "decompression failed: {why}"),
+            Self::Deserialize(why) => write!(f, "deserialization failed: {why}"),
+            Self::WorldWrite(why) => write!(f, "world write failed: {why}"),
+            Self::UnregisteredResource { type_name } => {
+                write!(f, "no registered resource found for `{type_name}`")
             }
-            Err(why) => {
-                error!("load failed: {why:?}");
+            Self::UnsupportedVersion(version) => write!(f, "unsupported version: {version}"),
+            Self::Migration { version, error } => {
+                write!(f, "migration from version {version} failed: {error}")
             }
         }
     }
 }
 
-/// Loads a previously saved [`DynamicScene`] into the given [`World`].
-pub fn load_world(world: &mut World, scene: DynamicScene) {
-    unload_world(world);
-    let mut entity_map = EntityMap::default();
-    if let Err(why) = scene.write_to_world(world, &mut entity_map) {
-        error!("world write failed: {why:?}");
+impl std::error::Error for LoadError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io(why) => Some(why),
+            Self::InvalidHeader | Self::UnsupportedFormat(_) | Self::UnsupportedCompression(_) => {
+                None
+            }
+            Self::Decompress(why) => Some(why.as_ref()),
+            Self::Deserialize(why) => Some(why.as_ref()),
+            Self::WorldWrite(why) => Some(why),
+            Self::Corrupted { .. }
+            | Self::UnregisteredResource { .. }
+            | Self::UnsupportedVersion(_) => None,
+            Self::Migration { error, .. } => Some(error.as_ref()),
+        }
+    }
+}
+
+impl From<io::Error> for LoadError {
+    fn from(why: io::Error) -> Self {
+        Self::Io(why)
+    }
+}
+
+impl From<SpannedError> for LoadError {
+    fn from(why: SpannedError) -> Self {
+        Self::Deserialize(why.into())
+    }
+}
+
+impl From<SceneSpawnError> for LoadError {
+    fn from(why: SceneSpawnError) -> Self {
+        Self::WorldWrite(why)
+    }
+}
+
+/// A [`System`] which handles the first queued load [`Request`] and starts the load process.
+///
+/// A [`LoadedEvent`] is sent if the request succeeds; a [`LoadFailedEvent`] is sent otherwise.
+/// In either case, the request is removed from the [`RequestQueue`].
+///
+/// If the request should be loaded in the background, the file is read and deserialized on the [`IoTaskPool`].
+/// In this case, this system polls the pending load every frame and only loads the [`World`] once the
+/// [`SavedWorld`] is ready. See [`LoadProgress`] for details.
+pub fn load(world: &mut World) {
+    let Some((
+        id,
+        Request::Load {
+            path,
+            background,
+            fallback,
+            mode,
+            marker,
+            group,
+        },
+    )) = world
+        .get_resource::<RequestQueue>()
+        .and_then(|queue| queue.first_load())
+        .map(|(id, request)| (id, request.clone()))
+    else {
+        // The background load request was cancelled:
+        cancel_pending_load(world);
+        return;
+    };
+    if world.resource::<RequestQueue>().has_save_before(id) {
+        // Wait for any earlier saves to be processed during `SaveStage::Save`:
+        return;
+    }
+    let result = if background {
+        match poll_load(world, id, &path, fallback) {
+            Some(result) => result,
+            None => return,
+        }
+    } else {
+        cancel_pending_load(world);
+        let candidates = load_candidates(world, &path, fallback);
+        read_latest_valid(&candidates, &ReadContext::new(world), None)
+    };
+    world.resource_mut::<RequestQueue>().cancel(id);
+    let result = result.and_then(|(loaded_path, saved)| {
+        write_world(world, saved, mode, group)?;
+        Ok(loaded_path)
+    });
+    if let (Ok(_), Some(marker)) = (&result, marker) {
+        let entities: Vec<Entity> = world
+            .resource::<Loaded>()
+            .iter()
+            .map(|(_, entity)| entity)
+            .collect();
+        for entity in entities {
+            marker.insert(&mut world.entity_mut(entity));
+        }
+    }
+    match result {
+        Ok(path) => {
+            info!("load successful: {path:?}");
+            world.send_event(LoadedEvent { id, path, mode });
+        }
+        Err(error) => {
+            error!("load failed: {error}");
+            world.send_event(LoadFailedEvent {
+                id,
+                path,
+                mode,
+                error,
+            });
+        }
+    }
+}
+
+/// A [`Resource`] which contains the task of a background load which is still in progress.
+#[derive(Resource)]
+pub struct PendingLoad(RequestId, Task<Result<(PathBuf, SavedWorld), LoadError>>);
+
+/// Starts a background load of the file at `path` for the [`Request`] with the given [`RequestId`],
+/// if it's not already started.
+///
+/// Returns the result of the background load if it is complete; `None` otherwise.
+fn poll_load(
+    world: &mut World,
+    id: RequestId,
+    path: &Path,
+    fallback: bool,
+) -> Option<Result<(PathBuf, SavedWorld), LoadError>> {
+    match world.get_resource::<PendingLoad>() {
+        Some(PendingLoad(pending_id, task)) if *pending_id == id => {
+            if !task.is_finished() {
+                return None;
+            }
+            let PendingLoad(_, task) = world.remove_resource::<PendingLoad>().unwrap();
+            world.remove_resource::<LoadProgress>();
+            return Some(future::block_on(task));
+        }
+        _ => cancel_pending_load(world),
+    }
+
+    let candidates = load_candidates(world, path, fallback);
+    let progress = LoadProgress::new(path);
+    let context = ReadContext::new(world);
+    let task_progress = progress.clone();
+    let task = IoTaskPool::get()
+        .spawn(async move { read_latest_valid(&candidates, &context, Some(&task_progress)) });
+    world.insert_resource(progress);
+    world.insert_resource(PendingLoad(id, task));
+    None
+}
+
+/// Cancels any background load which is still in progress.
+fn cancel_pending_load(world: &mut World) {
+    if world.remove_resource::<PendingLoad>().is_some() {
+        world.remove_resource::<LoadProgress>();
+    }
+}
+
+/// Returns the paths of all files which may be loaded for a load [`Request`] at `path`, in order.
+///
+/// If `fallback` is `true`, this includes any backups of the file. See [`LoadWorld::load_latest_valid`].
+/// Any background saves into these files are completed first.
+fn load_candidates(world: &mut World, path: &Path, fallback: bool) -> Vec<PathBuf> {
+    let mut candidates = vec![path.to_owned()];
+    if fallback {
+        let backups = world
+            .get_resource::<SaveSettings>()
+            .map(|settings| settings.backups)
+            .unwrap_or_default();
+        candidates.extend(backups.backups(path));
+    }
+    for candidate in &candidates {
+        wait_for_save(world, candidate);
+    }
+    candidates
+}
+
+/// Everything required to read a save file, which may be sent to the [`IoTaskPool`].
+struct ReadContext {
+    registry: AppTypeRegistry,
+    saved_resources: SavedResources,
+    aliases: SaveAliases,
+    migrations: Option<SaveMigrations>,
+}
+
+impl ReadContext {
+    fn new(world: &World) -> Self {
+        Self {
+            registry: world.resource::<AppTypeRegistry>().clone(),
+            saved_resources: world
+                .get_resource::<SavedResources>()
+                .cloned()
+                .unwrap_or_default(),
+            aliases: world
+                .get_resource::<SaveAliases>()
+                .cloned()
+                .unwrap_or_default(),
+            migrations: world.get_resource::<SaveMigrations>().cloned(),
+        }
+    }
+}
+
+/// Reads each of the given `candidates` in order, and returns the first valid one along with its path.
+///
+/// If no candidate is valid, the cause of failure of the first one is returned.
+fn read_latest_valid(
+    candidates: &[PathBuf],
+    context: &ReadContext,
+    progress: Option<&LoadProgress>,
+) -> Result<(PathBuf, SavedWorld), LoadError> {
+    let (path, backups) = candidates
+        .split_first()
+        .expect("there must be at least one load candidate");
+    let error = match read_valid_world(path, context, progress) {
+        Ok(saved) => return Ok((path.clone(), saved)),
+        Err(error) => error,
+    };
+    for backup in backups {
+        warn!("load failed: {path:?}: {error}; trying backup: {backup:?}");
+        match read_valid_world(backup, context, progress) {
+            Ok(saved) => return Ok((backup.clone(), saved)),
+            Err(why) => warn!("backup load failed: {backup:?}: {why}"),
+        }
+    }
+    Err(error)
+}
+
+/// Reads, deserializes, migrates and validates a previously saved file at `path`.
+fn read_valid_world(
+    path: &Path,
+    context: &ReadContext,
+    progress: Option<&LoadProgress>,
+) -> Result<SavedWorld, LoadError> {
+    let (header, mut saved) = read_saved_world(path, context, progress)?;
+    match &context.migrations {
+        Some(migrations) => migrations.migrate(header.version, &mut saved)?,
+        None if header.version > 0 => return Err(LoadError::UnsupportedVersion(header.version)),
+        None => {}
+    }
+    validate_scene(&saved, &context.registry.read(), &context.saved_resources)?;
+    Ok(saved)
+}
+
+/// Reads and deserializes a previously saved file at `path`, and updates the given [`LoadProgress`], if any.
+fn read_saved_world(
+    path: &Path,
+    context: &ReadContext,
+    progress: Option<&LoadProgress>,
+) -> Result<(SaveHeader, SavedWorld), LoadError> {
+    let mut file = File::open(path)?;
+    let total_bytes = file.metadata()?.len();
+    if let Some(progress) = progress {
+        progress.set_phase(LoadPhase::Reading);
+        progress.state.bytes_read.store(0, Ordering::Relaxed);
+        progress
+            .state
+            .total_bytes
+            .store(total_bytes, Ordering::Relaxed);
+    }
+
+    let mut serialized_scene = Vec::with_capacity(total_bytes as usize);
+    let mut buffer = vec![0; READ_CHUNK_SIZE];
+    loop {
+        let bytes_read = match file.read(&mut buffer) {
+            Ok(0) => break,
+            Ok(bytes_read) => bytes_read,
+            Err(why) if why.kind() == io::ErrorKind::Interrupted => continue,
+            Err(why) => return Err(why.into()),
+        };
+        serialized_scene.extend_from_slice(&buffer[..bytes_read]);
+        if let Some(progress) = progress {
+            progress
+                .state
+                .bytes_read
+                .fetch_add(bytes_read as u64, Ordering::Relaxed);
+        }
+    }
+
+    if let Some(progress) = progress {
+        progress.set_phase(LoadPhase::Deserializing);
+    }
+    let (header, body) = SaveHeader::read(&serialized_scene)?;
+    let body = header.compression.decompress(body)?;
+    let registry = context.registry.read();
+    let aliases = match &context.migrations {
+        Some(migrations) => migrations.aliases(header.version, &context.aliases),
+        None => context.aliases.clone(),
+    };
+    let saved = header.format.deserialize(&body, &registry, &aliases)?;
+    Ok((header, saved))
+}
+
+/// Size of each chunk read from a save file, after which [`LoadProgress`] is updated.
+const READ_CHUNK_SIZE: usize = 64 * 1024;
+
+/// Current phase of a background load. See [`LoadProgress`] for details.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadPhase {
+    /// The save file is being read from disk.
+    Reading,
+    /// The save file is being deserialized into a [`SavedWorld`].
+    Deserializing,
+}
+
+/// A [`Resource`] which reports the progress of a background load.
+///
+/// # Usage
+///
+/// This resource is inserted when a background load is started (see [`LoadWorld::load_in_background`]), and
+/// removed once the [`SavedWorld`] is ready to be loaded. It may be used to animate a loading screen while
+/// the save file is being read and deserialized:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::LoadProgress;
+/// fn update_loading_screen(progress: Option<Res<LoadProgress>>) {
+///     if let Some(progress) = progress {
+///         let phase = progress.phase();
+///         let fraction = progress.fraction();
+///         /* ... */
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone)]
+pub struct LoadProgress {
+    path: PathBuf,
+    state: Arc<LoadProgressState>,
+}
+
+#[derive(Default)]
+struct LoadProgressState {
+    phase: AtomicU8,
+    bytes_read: AtomicU64,
+    total_bytes: AtomicU64,
+}
+
+impl LoadProgress {
+    fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            state: Default::default(),
+        }
+    }
+
+    /// Returns the path of the file being loaded.
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Returns the current [`LoadPhase`].
+    pub fn phase(&self) -> LoadPhase {
+        match self.state.phase.load(Ordering::Relaxed) {
+            0 => LoadPhase::Reading,
+            _ => LoadPhase::Deserializing,
+        }
+    }
+
+    /// Returns the number of bytes read from the save file so far.
+    pub fn bytes_read(&self) -> u64 {
+        self.state.bytes_read.load(Ordering::Relaxed)
+    }
+
+    /// Returns the total size of the save file in bytes, or zero if it's not known yet.
+    pub fn total_bytes(&self) -> u64 {
+        self.state.total_bytes.load(Ordering::Relaxed)
+    }
+
+    /// Returns the fraction of the save file which has been read so far, between `0.0` and `1.0`.
+    pub fn fraction(&self) -> f32 {
+        match self.total_bytes() {
+            0 => 0.0,
+            total_bytes => (self.bytes_read() as f64 / total_bytes as f64).min(1.0) as f32,
+        }
+    }
+
+    fn set_phase(&self, phase: LoadPhase) {
+        let phase = match phase {
+            LoadPhase::Reading => 0,
+            LoadPhase::Deserializing => 1,
+        };
+        self.state.phase.store(phase, Ordering::Relaxed);
+    }
+}
+
+/// Loads a previously [`SavedWorld`] into the given [`World`].
+///
+/// The saved world is validated using [`validate_scene`] before the world is unloaded. If it is not
+/// valid, the world is left untouched and an error is returned.
+///
+/// After all entities are spawned, entity references within every component registered with
+/// [`ReflectMapEntities`] (such as [`Parent`] and [`Children`]) are remapped to the loaded entities.
+/// References to entities which were not saved cannot be remapped, and are reported as warnings. Each component
+/// is remapped on its own, so only the component which contains such a reference is left as is.
+pub fn load_world(world: &mut World, saved: SavedWorld) -> Result<(), LoadError> {
+    write_world(world, saved, LoadMode::Replace, None)
+}
+
+/// Loads a previously [`SavedWorld`] into the given [`World`], without unloading any existing entities.
+///
+/// This is the same as [`load_world`], except that saved resources are not loaded, and any loaded [`SaveId`]
+/// which is already in use is replaced with a new one. See [`LoadWorld::load_additive`] for details.
+pub fn load_world_additive(world: &mut World, saved: SavedWorld) -> Result<(), LoadError> {
+    write_world(world, saved, LoadMode::Additive, None)
+}
+
+/// Writes the given [`SavedWorld`] into the given [`World`] according to the given [`LoadMode`].
+///
+/// If a save `group` is given, only entities in that group are unloaded, and loaded entities are marked with its
+/// [`SaveGroup`] component instead of [`Save`].
+fn write_world(
+    world: &mut World,
+    saved: SavedWorld,
+    mode: LoadMode,
+    group: Option<SaveGroupFilter>,
+) -> Result<(), LoadError> {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    let saved_resources = world
+        .get_resource::<SavedResources>()
+        .cloned()
+        .unwrap_or_default();
+    validate_scene(&saved, &registry, &saved_resources)?;
+    match (mode, group) {
+        (LoadMode::Replace, Some(group)) => {
+            let entities = group.unloaded_entities(world);
+            despawn_recursive(world, entities);
+        }
+        (LoadMode::Replace, None) => {
+            unload_world(world);
+            load_resources(world, &saved.resources);
+        }
+        (LoadMode::Additive, _) => {}
+    }
+    let entity_map = write_entities(world, &saved, &registry)?;
+    let policy = world
+        .get_resource::<SaveSettings>()
+        .map(|settings| settings.dangling)
+        .unwrap_or_default();
+    let dangling = map_entities(world, &entity_map, &registry, policy);
+    for event in &dangling {
+        if event.policy == DanglingPolicy::Despawn {
+            if let Some(entity) = world.get_entity_mut(event.entity) {
+                entity.despawn_recursive();
+            }
+        }
     }
-    let mut loaded = HashMap::new();
-    // TODO: EntityMap doesn't implement `iter()`
+    let mut loaded = Loaded::default();
     for old_entity in entity_map.keys() {
         let entity = entity_map.get(old_entity).unwrap();
-        debug!("entity update required: {old_entity:?} -> {entity:?}");
-        loaded.insert(old_entity.index(), entity);
-        world.entity_mut(entity).insert(Save);
+        let Some(mut entity) = world.get_entity_mut(entity) else {
+            continue;
+        };
+        debug!(
+            "entity update required: {old_entity:?} -> {:?}",
+            entity.id()
+        );
+        loaded.insert(old_entity, entity.id());
+        match &group {
+            Some(group) => group.insert(&mut entity),
+            None => {
+                entity.insert(Save);
+            }
+        }
+    }
+    match mode {
+        LoadMode::Replace => index_save_ids(world),
+        LoadMode::Additive => {
+            index_loaded_save_ids(world, loaded.iter().map(|(_, entity)| entity).collect())
+        }
+    }
+    if let Some(group) = group {
+        index_group_save_ids(world, group);
     }
-    world.insert_resource(Loaded(loaded));
+    world.insert_resource(loaded);
+    for event in dangling {
+        world.send_event(event);
+    }
+    Ok(())
 }
 
-/// A [`System`] which finalizes load process by removing [`Loaded`] components and consuming the [`Request`].
+/// Spawns all entities of the given [`SavedWorld`] and returns an [`EntityMap`] of saved entities to their
+/// loaded counterparts.
+///
+/// Files saved without [`SavedWorld::generations`] only store entity indices, so the generation of each saved
+/// entity is inferred from references to it within saved components. If more than one generation is referenced,
+/// the latest one is assumed to be the saved entity, since any earlier generation must have been despawned
+/// before save.
+fn write_entities(
+    world: &mut World,
+    saved: &SavedWorld,
+    registry: &TypeRegistry,
+) -> Result<EntityMap, LoadError> {
+    let mut generations = HashMap::new();
+    for entity in saved.entities() {
+        generations.insert(entity.entity, saved.entity(entity).generation());
+    }
+    for component in saved
+        .entities()
+        .iter()
+        .flat_map(|entity| &entity.components)
+    {
+        visit_entities(component.as_reflect(), &mut |entity| {
+            if let Some(generation) = generations.get_mut(&entity.index()) {
+                *generation = entity.generation().max(*generation);
+            }
+        });
+    }
+
+    let mut entity_map = EntityMap::default();
+    for saved_entity in saved.entities() {
+        let entity = world.spawn_empty().id();
+        let generation = generations[&saved_entity.entity];
+        entity_map.insert(
+            Entity::from_bits((generation as u64) << 32 | saved_entity.entity as u64),
+            entity,
+        );
+        for component in &saved_entity.components {
+            let type_name = component.type_name();
+            let reflect_component = registry
+                .get_with_name(type_name)
+                .and_then(|registration| registration.data::<ReflectComponent>())
+                .ok_or_else(|| SceneSpawnError::UnregisteredComponent {
+                    type_name: type_name.to_string(),
+                })?;
+            reflect_component.apply_or_insert(world, entity, &**component);
+        }
+    }
+    Ok(entity_map)
+}
+
+/// Remaps entity references within every component registered with [`ReflectMapEntities`] of the loaded entities
+/// in the given [`EntityMap`].
+///
+/// References to entities which were not saved are dangling. They are replaced with [`PLACEHOLDER_ENTITY`], so
+/// all other references within the same component are still remapped, and the component is then handled according
+/// to the given [`DanglingPolicy`]. Returns a [`DanglingEntitiesEvent`] for every component with dangling references.
+///
+/// Each component is remapped on its own, so a reference which is not visible to reflection, and can not be
+/// remapped, only affects the component which contains it. Such a component is left as is, and a warning is logged.
+fn map_entities(
+    world: &mut World,
+    entity_map: &EntityMap,
+    registry: &TypeRegistry,
+    policy: DanglingPolicy,
+) -> Vec<DanglingEntitiesEvent> {
+    let mappable: Vec<_> = registry
+        .iter()
+        .filter_map(|registration| {
+            Some((
+                registration.type_name(),
+                registration.data::<ReflectComponent>()?,
+                registration.data::<ReflectMapEntities>()?,
+            ))
+        })
+        .collect();
+    // `ReflectMapEntities` remaps all entities in the map at once, and stops at the first error.
+    // To remap each component on its own, it is copied into a scratch world which contains nothing else:
+    let mut scratch = World::new();
+    let mut events = Vec::new();
+    for saved_entity in entity_map.keys() {
+        let entity = entity_map.get(saved_entity).unwrap();
+        for (type_name, reflect_component, reflect_map_entities) in &mappable {
+            let Some(component) = reflect_component.reflect(world, entity) else {
+                continue;
+            };
+            let mut component_map = EntityMap::default();
+            component_map.insert(saved_entity, entity);
+            let mut dangling = Vec::new();
+            visit_entities(component, &mut |reference| {
+                let mapped = entity_map.get(reference).unwrap_or_else(|_| {
+                    dangling.push(reference);
+                    PLACEHOLDER_ENTITY
+                });
+                component_map.insert(reference, mapped);
+            });
+            scratch.get_or_spawn(entity);
+            reflect_component.copy(world, &mut scratch, entity, entity);
+            match reflect_map_entities.map_entities(&mut scratch, &component_map) {
+                Ok(()) => reflect_component.copy(&scratch, world, entity, entity),
+                Err(why) => warn!("{type_name} of {entity:?}: {why}"),
+            }
+            reflect_component.remove(&mut scratch, entity);
+            if dangling.is_empty() {
+                continue;
+            }
+            let event = dangling_event(entity, type_name, DanglingEntities(dangling), policy);
+            if event.policy == DanglingPolicy::Remove {
+                reflect_component.remove(world, entity);
+            }
+            events.push(event);
+        }
+    }
+    events
+}
+
+/// Calls `f` for every [`Entity`] referenced within the given reflected value, recursively.
+fn visit_entities(value: &dyn Reflect, f: &mut impl FnMut(Entity)) {
+    match value.reflect_ref() {
+        ReflectRef::Struct(value) => value
+            .iter_fields()
+            .for_each(|field| visit_entities(field, f)),
+        ReflectRef::TupleStruct(value) => value
+            .iter_fields()
+            .for_each(|field| visit_entities(field, f)),
+        ReflectRef::Tuple(value) => value
+            .iter_fields()
+            .for_each(|field| visit_entities(field, f)),
+        ReflectRef::List(value) => value.iter().for_each(|item| visit_entities(item, f)),
+        ReflectRef::Array(value) => value.iter().for_each(|item| visit_entities(item, f)),
+        ReflectRef::Map(value) => value.iter().for_each(|(key, value)| {
+            visit_entities(key, f);
+            visit_entities(value, f);
+        }),
+        ReflectRef::Enum(value) => value
+            .iter_fields()
+            .for_each(|field| visit_entities(field.value(), f)),
+        ReflectRef::Value(value) => {
+            if let Some(&entity) = value.downcast_ref::<Entity>() {
+                f(entity);
+            }
+        }
+    }
+}
+
+/// Checks if the given [`SavedWorld`] can be written into a [`World`] with the given [`TypeRegistry`].
+///
+/// A saved world is valid if all of its components are registered and reflect [`Component`], and all of
+/// its resources are registered using [`RegisterSavedResource`] (i.e. they are in the given [`SavedResources`]).
+pub fn validate_scene(
+    saved: &SavedWorld,
+    registry: &TypeRegistry,
+    saved_resources: &SavedResources,
+) -> Result<(), LoadError> {
+    for resource in &saved.resources {
+        let type_name = resource.type_name();
+        let registration =
+            registry
+                .get_with_name(type_name)
+                .ok_or_else(|| SceneSpawnError::UnregisteredType {
+                    type_name: type_name.to_string(),
+                })?;
+        if registration.data::<ReflectResource>().is_none()
+            || saved_resources.mode(registration.type_id()).is_none()
+        {
+            return Err(LoadError::UnregisteredResource {
+                type_name: type_name.to_string(),
+            });
+        }
+    }
+    for entity in saved.entities() {
+        for component in &entity.components {
+            let type_name = component.type_name();
+            let registration = registry.get_with_name(type_name).ok_or_else(|| {
+                SceneSpawnError::UnregisteredType {
+                    type_name: type_name.to_string(),
+                }
+            })?;
+            if registration.data::<ReflectComponent>().is_none() {
+                return Err(SceneSpawnError::UnregisteredComponent {
+                    type_name: type_name.to_string(),
+                }
+                .into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// A [`System`] which finalizes load process by removing the [`Loaded`] resource.
 pub(crate) fn finish_load(mut commands: Commands) {
-    commands.remove_resource::<Request>();
     commands.remove_resource::<Loaded>();
 }
 
@@ -114,6 +877,11 @@ fn unload_world(world: &mut World) {
         .query_filtered::<Entity, Or<(With<Save>, With<Unload>)>>()
         .iter(world)
         .collect();
+    despawn_recursive(world, entities);
+}
+
+/// Despawns all given entities and their children.
+fn despawn_recursive(world: &mut World, entities: Vec<Entity>) {
     for entity in entities {
         // Check the entity again in case it was despawned recursively
         if let Some(entity) = world.get_entity_mut(entity) {
@@ -126,46 +894,372 @@ fn unload_world(world: &mut World) {
 ///
 /// # Usage
 ///
-/// Components which implement this trait must be registered using [`RegisterLoaded`].
+/// Components which implement this trait must be registered using [`RegisterLoaded`], or reflect
+/// [`ReflectFromLoaded`].
+///
+/// This trait may be derived for structs and enums. By default, the derived implementation calls
+/// [`FromLoaded::try_from_loaded`] on every field. Fields may be configured using the `#[from_loaded(...)]` attribute:
+/// - `#[from_loaded(skip)]` leaves the field untouched.
+/// - `#[from_loaded(with = "path::to::function")]` calls `function(&mut field, loaded)` instead.
+/// - `#[from_loaded(try_with = "path::to::function")]` calls `function(&mut field, loaded)` instead, which returns
+///   a `Result<(), DanglingEntities>` to report dangling references.
 ///
 /// Use this trait to update references to entities during [`SaveStage::PostLoad`].
-/// This trait is implemented for `Entity`, and `Option<Entity>`. This can be used to recursively
-/// call [`FromLoaded::from_loaded`] on any entity references which need to be updated.
+/// This trait is implemented for `Entity`, `Option<T>`, `Box<T>`, tuples, and common collections (arrays, `Vec<T>`,
+/// `VecDeque<T>`, `SmallVec<A>`, values of `HashMap<K, V>`, and `HashSet<T>`). This can be used to recursively
+/// call [`FromLoaded::try_from_loaded`] on any entity references which need to be updated.
 ///
 /// See [`Loaded`] for more details.
 ///
+/// # Dangling References
+///
+/// A reference to an entity which was not saved cannot be updated. [`FromLoaded::try_from_loaded`] replaces it
+/// and returns an error, while [`FromLoaded::from_loaded`] panics if it encounters such a reference.
+/// Registered components are updated according to the [`DanglingPolicy`] in [`SaveSettings`].
+///
+/// Fields updated using a `with` function can not report dangling references. Use a `try_with` function instead.
+///
 /// # Example
 /// ```
 /// # use bevy::prelude::*;
-/// # use bevy_atomic_save::{FromLoaded, Loaded};
+/// # use bevy_atomic_save::{DanglingEntities, FromLoaded, Loaded};
 /// #[derive(Component)]
 /// struct SomeEntity(Entity);
 ///
 /// impl FromLoaded for SomeEntity {
-///     fn from_loaded(&mut self, loaded: &Loaded) {
-///         self.0.from_loaded(loaded);
+///     fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+///         self.0.try_from_loaded(loaded)
 ///     }
 /// }
 /// ```
+#[allow(clippy::wrong_self_convention)]
 pub trait FromLoaded {
-    fn from_loaded(&mut self, loaded: &Loaded);
+    /// Updates all entity references, and returns an error if any of them are dangling.
+    ///
+    /// Dangling references are replaced with [`PLACEHOLDER_ENTITY`], or `None` if they are optional.
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities>;
+
+    /// Updates all entity references, and panics if any of them are dangling.
+    fn from_loaded(&mut self, loaded: &Loaded) {
+        if let Err(why) = self.try_from_loaded(loaded) {
+            panic!("{why}");
+        }
+    }
+}
+
+/// An [`Entity`] which replaces dangling entity references. See [`FromLoaded::try_from_loaded`] for details.
+pub const PLACEHOLDER_ENTITY: Entity = Entity::from_raw(u32::MAX);
+
+/// Error returned by [`FromLoaded::try_from_loaded`] which contains all saved entity references which could
+/// not be updated, because their entities were not saved.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DanglingEntities(pub Vec<Entity>);
+
+impl DanglingEntities {
+    /// Combines the given results into a single result, which contains all dangling entities, if any.
+    pub fn collect(results: impl IntoIterator<Item = Result<(), Self>>) -> Result<(), Self> {
+        let mut entities = Vec::new();
+        for result in results {
+            if let Err(Self(dangling)) = result {
+                entities.extend(dangling);
+            }
+        }
+        if entities.is_empty() {
+            Ok(())
+        } else {
+            Err(Self(entities))
+        }
+    }
+}
+
+impl fmt::Display for DanglingEntities {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "dangling entity references: {:?}", self.0)
+    }
+}
+
+impl std::error::Error for DanglingEntities {}
+
+/// Defines how components with dangling entity references are handled when loaded.
+///
+/// # Usage
+///
+/// Components registered with [`ReflectMapEntities`] are handled while the world is loaded, and components
+/// which implement [`FromLoaded`] are handled during [`SaveStage::PostLoad`].
+///
+/// The policy may be configured using [`SavePlugin::with_dangling_policy`]. A [`DanglingEntitiesEvent`] is
+/// sent for every component with dangling references, unless the policy is [`DanglingPolicy::Panic`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DanglingPolicy {
+    /// Panic on the first dangling reference.
+    #[default]
+    Panic,
+    /// Replace dangling references with [`PLACEHOLDER_ENTITY`], or `None` if they are optional.
+    Placeholder,
+    /// Remove the component which contains dangling references.
+    Remove,
+    /// Despawn the entity (and all of its children) which owns the component with dangling references.
+    Despawn,
+}
+
+/// An [`Event`] sent for each loaded component with dangling entity references. See [`DanglingPolicy`] for details.
+#[derive(Debug)]
+pub struct DanglingEntitiesEvent {
+    /// The loaded entity which owns the component.
+    pub entity: Entity,
+    /// Type name of the component.
+    pub component: &'static str,
+    /// All saved entity references which could not be updated.
+    pub dangling: DanglingEntities,
+    /// The policy which was applied to the component.
+    pub policy: DanglingPolicy,
 }
 
 impl FromLoaded for Entity {
-    fn from_loaded(&mut self, loaded: &Loaded) {
-        *self = loaded.entity(*self).expect("loaded entity is not valid");
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        match loaded.entity(*self) {
+            Some(entity) => {
+                *self = entity;
+                Ok(())
+            }
+            None => Err(DanglingEntities(vec![std::mem::replace(
+                self,
+                PLACEHOLDER_ENTITY,
+            )])),
+        }
     }
 }
 
+/// If the value contains any dangling references, it is replaced with `None`.
 impl<T: FromLoaded> FromLoaded for Option<T> {
-    fn from_loaded(&mut self, loaded: &Loaded) {
-        if let Some(entity) = self {
-            entity.from_loaded(loaded);
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        let Some(value) = self else {
+            return Ok(());
+        };
+        let result = value.try_from_loaded(loaded);
+        if result.is_err() {
+            *self = None;
+        }
+        result
+    }
+}
+
+impl<T: FromLoaded + ?Sized> FromLoaded for Box<T> {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        (**self).try_from_loaded(loaded)
+    }
+}
+
+impl<T: FromLoaded> FromLoaded for [T] {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        DanglingEntities::collect(self.iter_mut().map(|item| item.try_from_loaded(loaded)))
+    }
+}
+
+impl<T: FromLoaded, const N: usize> FromLoaded for [T; N] {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        self.as_mut_slice().try_from_loaded(loaded)
+    }
+}
+
+impl<T: FromLoaded> FromLoaded for Vec<T> {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        self.as_mut_slice().try_from_loaded(loaded)
+    }
+}
+
+impl<T: FromLoaded> FromLoaded for VecDeque<T> {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        DanglingEntities::collect(self.iter_mut().map(|item| item.try_from_loaded(loaded)))
+    }
+}
+
+impl<A: smallvec::Array> FromLoaded for SmallVec<A>
+where
+    A::Item: FromLoaded,
+{
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        self.as_mut_slice().try_from_loaded(loaded)
+    }
+}
+
+/// Only the values are updated, since keys are not mutable.
+impl<K, V: FromLoaded, S> FromLoaded for std::collections::HashMap<K, V, S> {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        DanglingEntities::collect(self.values_mut().map(|value| value.try_from_loaded(loaded)))
+    }
+}
+
+/// Only the values are updated, since keys are not mutable.
+impl<K, V: FromLoaded, S> FromLoaded for hashbrown::HashMap<K, V, S> {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        DanglingEntities::collect(self.values_mut().map(|value| value.try_from_loaded(loaded)))
+    }
+}
+
+/// All items are removed and inserted again, since their hashes change when updated.
+impl<T: FromLoaded + Eq + Hash, S: BuildHasher> FromLoaded for std::collections::HashSet<T, S> {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        let mut items: Vec<T> = self.drain().collect();
+        let result =
+            DanglingEntities::collect(items.iter_mut().map(|item| item.try_from_loaded(loaded)));
+        self.extend(items);
+        result
+    }
+}
+
+/// All items are removed and inserted again, since their hashes change when updated.
+impl<T: FromLoaded + Eq + Hash, S: BuildHasher> FromLoaded for hashbrown::HashSet<T, S> {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        let mut items: Vec<T> = self.drain().collect();
+        let result =
+            DanglingEntities::collect(items.iter_mut().map(|item| item.try_from_loaded(loaded)));
+        self.extend(items);
+        result
+    }
+}
+
+macro_rules! impl_from_loaded_for_tuple {
+    ($($name:ident),*) => {
+        impl<$($name: FromLoaded),*> FromLoaded for ($($name,)*) {
+            #[allow(non_snake_case)]
+            fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+                let ($($name,)*) = self;
+                DanglingEntities::collect([$($name.try_from_loaded(loaded)),*])
+            }
+        }
+    };
+}
+
+impl_from_loaded_for_tuple!(A);
+impl_from_loaded_for_tuple!(A, B);
+impl_from_loaded_for_tuple!(A, B, C);
+impl_from_loaded_for_tuple!(A, B, C, D);
+impl_from_loaded_for_tuple!(A, B, C, D, E);
+impl_from_loaded_for_tuple!(A, B, C, D, E, F);
+impl_from_loaded_for_tuple!(A, B, C, D, E, F, G);
+impl_from_loaded_for_tuple!(A, B, C, D, E, F, G, H);
+impl_from_loaded_for_tuple!(A, B, C, D, E, F, G, H, I);
+impl_from_loaded_for_tuple!(A, B, C, D, E, F, G, H, I, J);
+impl_from_loaded_for_tuple!(A, B, C, D, E, F, G, H, I, J, K);
+impl_from_loaded_for_tuple!(A, B, C, D, E, F, G, H, I, J, K, L);
+
+/// Updates the given `component` of an `entity` from [`Loaded`], and returns a [`DanglingEntitiesEvent`]
+/// if it has any dangling references which should be handled according to the given `policy`.
+fn update_loaded<T: Component + FromLoaded>(
+    entity: Entity,
+    component: &mut T,
+    loaded: &Loaded,
+    policy: DanglingPolicy,
+) -> Option<DanglingEntitiesEvent> {
+    let dangling = component.try_from_loaded(loaded).err()?;
+    Some(dangling_event(
+        entity,
+        std::any::type_name::<T>(),
+        dangling,
+        policy,
+    ))
+}
+
+/// Returns a [`DanglingEntitiesEvent`] for the given `component` of an `entity` with `dangling` references,
+/// or panics if the given `policy` is [`DanglingPolicy::Panic`].
+fn dangling_event(
+    entity: Entity,
+    component: &'static str,
+    dangling: DanglingEntities,
+    policy: DanglingPolicy,
+) -> DanglingEntitiesEvent {
+    if policy == DanglingPolicy::Panic {
+        panic!("{component} of {entity:?}: {dangling}");
+    }
+    warn!("{component} of {entity:?}: {dangling} ({policy:?})");
+    DanglingEntitiesEvent {
+        entity,
+        component,
+        dangling,
+        policy,
+    }
+}
+
+/// Type data used to call [`FromLoaded::from_loaded`] on all loaded instances of a reflected [`Component`].
+///
+/// # Usage
+///
+/// Any component registered with this type data is updated automatically during [`SaveStage::PostLoad`],
+/// without using [`RegisterLoaded`]. This is typically used with `#[derive(FromLoaded)]`:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::*;
+/// #[derive(Component, Default, Reflect, FromLoaded)]
+/// #[reflect(Component, FromLoaded)]
+/// struct SomeEntity(Option<Entity>);
+///
+/// # let mut app = App::new();
+/// app.register_type::<SomeEntity>();
+/// ```
+///
+/// Components should not be registered using both this type data and [`RegisterLoaded`], otherwise
+/// their references are updated twice.
+#[derive(Clone)]
+pub struct ReflectFromLoaded {
+    from_loaded: fn(&mut World, &Loaded),
+}
+
+impl ReflectFromLoaded {
+    /// Calls [`FromLoaded::from_loaded`] on all instances of the reflected component in the given [`World`]
+    /// which were loaded.
+    pub fn from_loaded(&self, world: &mut World, loaded: &Loaded) {
+        (self.from_loaded)(world, loaded);
+    }
+}
+
+impl<T: Component + FromLoaded> FromType<T> for ReflectFromLoaded {
+    fn from_type() -> Self {
+        Self {
+            from_loaded: |world, loaded| {
+                let policy = world
+                    .get_resource::<SaveSettings>()
+                    .map(|settings| settings.dangling)
+                    .unwrap_or_default();
+                let events: Vec<DanglingEntitiesEvent> = loaded
+                    .iter()
+                    .filter_map(|(_, entity)| {
+                        let mut component = world.get_mut::<T>(entity)?;
+                        update_loaded(entity, &mut *component, loaded, policy)
+                    })
+                    .collect();
+                for event in events {
+                    match event.policy {
+                        DanglingPolicy::Remove => {
+                            world.entity_mut(event.entity).remove::<T>();
+                        }
+                        DanglingPolicy::Despawn => {
+                            if let Some(entity) = world.get_entity_mut(event.entity) {
+                                entity.despawn_recursive();
+                            }
+                        }
+                        DanglingPolicy::Panic | DanglingPolicy::Placeholder => {}
+                    }
+                    world.send_event(event);
+                }
+            },
         }
     }
 }
 
-/// A [`System`] which calls [`FromLoaded::from_loaded`] on all instances of a [`Component`]
+/// A [`System`] which calls [`FromLoaded::from_loaded`] on all components registered with [`ReflectFromLoaded`].
+pub(crate) fn reflect_loaded(world: &mut World) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    world.resource_scope(|world, loaded: Mut<Loaded>| {
+        for registration in registry.read().iter() {
+            if let Some(reflect_from_loaded) = registration.data::<ReflectFromLoaded>() {
+                reflect_from_loaded.from_loaded(world, &loaded);
+            }
+        }
+    });
+}
+
+/// A [`System`] which calls [`FromLoaded::from_loaded`] on all loaded instances of a [`Component`]
 /// which implements [`FromLoaded`].
 ///
 /// # Usage
@@ -174,15 +1268,40 @@ impl<T: FromLoaded> FromLoaded for Option<T> {
 /// Because of this, it is typically safer to use [`RegisterLoaded`] to add this system to an app.
 /// However, if there is any load order dependencies between components, this system may be inserted
 /// manually to control its run schedule.
-pub fn loaded<T: Component + FromLoaded>(loaded: Res<Loaded>, mut query: Query<&mut T>) {
-    for mut component in &mut query {
-        component.from_loaded(&loaded);
+pub fn loaded<T: Component + FromLoaded>(
+    loaded: Res<Loaded>,
+    settings: Option<Res<SaveSettings>>,
+    mut query: Query<(Entity, &mut T)>,
+    mut commands: Commands,
+    mut events: EventWriter<DanglingEntitiesEvent>,
+) {
+    let policy = settings
+        .map(|settings| settings.dangling)
+        .unwrap_or_default();
+    for (_, entity) in loaded.iter() {
+        let Ok((entity, mut component)) = query.get_mut(entity) else {
+            continue;
+        };
+        let Some(event) = update_loaded(entity, &mut *component, &loaded, policy) else {
+            continue;
+        };
+        match event.policy {
+            DanglingPolicy::Remove => {
+                commands.entity(entity).remove::<T>();
+            }
+            DanglingPolicy::Despawn => {
+                commands.entity(entity).despawn_recursive();
+            }
+            DanglingPolicy::Panic | DanglingPolicy::Placeholder => {}
+        }
+        events.send(event);
     }
 }
 
 /// Extension trait used to register components which implement [`FromLoaded`] with an [`App`].
 pub trait RegisterLoaded {
-    /// Adds a system which calls [`FromLoaded::from_loaded`] on all instances of a component during [`SaveStage::PostLoad`].
+    /// Adds a system which calls [`FromLoaded::from_loaded`] on all loaded instances of a component during
+    /// [`SaveStage::PostLoad`].
     fn register_loaded<T: FromLoaded + Component>(self) -> Self;
 }
 
@@ -0,0 +1,138 @@
+use std::time::SystemTime;
+
+use bevy::reflect::serde::{ReflectSerializer, UntypedReflectDeserializer};
+use bevy::reflect::{GetTypeRegistration, TypeRegistryArc};
+use serde::de::DeserializeSeed;
+use serde::{Deserialize, Serialize};
+
+use super::*;
+
+/// Metadata of a save file, which may be read without loading the [`World`].
+///
+/// # Usage
+///
+/// Metadata is stored in the [`SaveHeader`] of every save file, and it may be read cheaply using
+/// [`read_save_metadata`]. This is useful for displaying information about save files (i.e. in a save slot
+/// menu) without loading them.
+///
+/// Each save file stores the time at which it was saved. Any additional metadata is provided by the game
+/// using [`RegisterSaveMetadata`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SaveMetadata {
+    /// Time at which the file was saved.
+    #[serde(default)]
+    pub timestamp: Option<SystemTime>,
+    /// Metadata registered using [`RegisterSaveMetadata`], serialized as RON.
+    #[serde(default)]
+    data: Option<String>,
+}
+
+impl SaveMetadata {
+    /// Deserializes and returns the metadata registered using [`RegisterSaveMetadata`], if any.
+    ///
+    /// The type of the metadata must be registered in the given type `registry`.
+    pub fn data(&self, registry: &TypeRegistryArc) -> Result<Option<Box<dyn Reflect>>, LoadError> {
+        let Some(data) = &self.data else {
+            return Ok(None);
+        };
+        let mut deserializer = ron::Deserializer::from_str(data)?;
+        UntypedReflectDeserializer::new(&registry.read())
+            .deserialize(&mut deserializer)
+            .map(Some)
+            .map_err(|why| deserializer.span_error(why).into())
+    }
+
+    /// Returns the metadata registered using [`RegisterSaveMetadata`], if it exists and is of type `M`.
+    pub fn get<M: FromReflect>(&self, registry: &TypeRegistryArc) -> Option<M> {
+        let data = self.data(registry).ok()??;
+        M::from_reflect(&*data)
+    }
+}
+
+/// Reads the [`SaveMetadata`] of a save file at the given `path`, without loading the rest of the file.
+///
+/// Files saved without any metadata return [`SaveMetadata::default()`].
+pub fn read_save_metadata(path: impl AsRef<Path>) -> Result<SaveMetadata, LoadError> {
+    read_save_header(path).map(|header| header.metadata)
+}
+
+/// Extension trait used to register the [`SaveMetadata`] of an [`App`].
+///
+/// # Usage
+///
+/// Metadata may be any reflectable type. It is either saved from a [`Resource`], or created using a
+/// callback during each save request. Only one type of metadata may be registered; any previous
+/// registration is replaced.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{RegisterSaveMetadata, SavePlugin};
+/// #[derive(Resource, Default, Reflect, FromReflect)]
+/// struct SaveInfo {
+///     level: String,
+///     play_time: f32,
+/// }
+///
+/// App::new()
+///     .add_plugin(SavePlugin::default())
+///     .init_resource::<SaveInfo>()
+///     .register_save_metadata::<SaveInfo>();
+/// ```
+pub trait RegisterSaveMetadata {
+    /// Registers a [`Resource`] which is saved as [`SaveMetadata`] with every save request.
+    ///
+    /// If the resource does not exist during a save request, no metadata is saved other than its timestamp.
+    fn register_save_metadata<M: Resource + Reflect + GetTypeRegistration>(self) -> Self;
+
+    /// Registers a callback which creates the [`SaveMetadata`] of every save request from the saved [`World`].
+    fn register_save_metadata_with<M: Reflect + GetTypeRegistration>(
+        self,
+        metadata: impl Fn(&World) -> M + Send + Sync + 'static,
+    ) -> Self;
+}
+
+impl RegisterSaveMetadata for &mut App {
+    fn register_save_metadata<M: Resource + Reflect + GetTypeRegistration>(self) -> Self {
+        self.register_type::<M>();
+        self.insert_resource(SaveMetadataSource(Box::new(|world| {
+            world
+                .get_resource::<M>()
+                .map(|metadata| metadata.clone_value())
+        })))
+    }
+
+    fn register_save_metadata_with<M: Reflect + GetTypeRegistration>(
+        self,
+        metadata: impl Fn(&World) -> M + Send + Sync + 'static,
+    ) -> Self {
+        self.register_type::<M>();
+        self.insert_resource(SaveMetadataSource(Box::new(move |world| {
+            Some(Box::new(metadata(world)))
+        })))
+    }
+}
+
+/// A [`Resource`] which creates the [`SaveMetadata`] registered using [`RegisterSaveMetadata`].
+#[derive(Resource)]
+pub(crate) struct SaveMetadataSource(Box<SaveMetadataFn>);
+
+type SaveMetadataFn = dyn Fn(&World) -> Option<Box<dyn Reflect>> + Send + Sync;
+
+/// Returns the [`SaveMetadata`] of a save request for the given [`World`].
+pub(crate) fn save_metadata(world: &World) -> Result<SaveMetadata, SaveError> {
+    let data = world
+        .get_resource::<SaveMetadataSource>()
+        .and_then(|source| (source.0)(world));
+    let data = match data {
+        Some(data) => {
+            let registry = world.resource::<AppTypeRegistry>().read();
+            Some(ron::to_string(&ReflectSerializer::new(&*data, &registry))?)
+        }
+        None => None,
+    };
+    Ok(SaveMetadata {
+        timestamp: Some(SystemTime::now()),
+        data,
+    })
+}
@@ -0,0 +1,216 @@
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use bevy::reflect::GetTypeRegistration;
+use bevy::utils::HashMap;
+
+use super::*;
+
+/// A migration which upgrades a [`SavedWorld`] from one schema version to the next.
+pub type Migration =
+    dyn Fn(&mut SavedWorld) -> Result<(), Box<dyn std::error::Error + Send + Sync>> + Send + Sync;
+
+/// A [`Resource`] which contains the current schema version of save files, and all migrations registered
+/// using [`RegisterMigration`].
+///
+/// # Usage
+///
+/// Every save file is stamped with the current schema version in its [`SaveHeader`]. Files saved without
+/// a version (including files saved before any version was set) have version `0`.
+///
+/// During load, after a save file is deserialized, it is upgraded from its version to the current version
+/// by applying each migration in order, before any changes are made to the [`World`]. Migrations operate on
+/// the deserialized [`SavedWorld`], where each component and resource is a reflected value.
+///
+/// This means any saved type must still be registered in the type registry to be deserialized. Typically,
+/// a legacy type is kept registered (without being a component), and a migration replaces it with its
+/// current equivalent. See [`SavedWorld::components_mut`].
+///
+/// If the fields of a type change without its name changing, older save files no longer match it. In this case,
+/// its previous layout may be registered as a legacy type using [`RegisterMigration::register_legacy_type`].
+/// Any saved value of that type in an older save file is then deserialized as the legacy type, before
+/// any migration is applied.
+///
+/// If a save file has a version newer than the current version, or if any migration is missing, the load
+/// fails with [`LoadError::UnsupportedVersion`].
+#[derive(Resource, Clone, Default)]
+pub struct SaveMigrations {
+    version: u32,
+    migrations: BTreeMap<u32, Arc<Migration>>,
+    legacy_types: HashMap<String, BTreeMap<u32, String>>,
+}
+
+impl SaveMigrations {
+    /// Returns the current schema version of save files.
+    pub fn version(&self) -> u32 {
+        self.version
+    }
+
+    /// Upgrades the given [`SavedWorld`] from the given `version` to the current version.
+    pub fn migrate(&self, version: u32, saved: &mut SavedWorld) -> Result<(), LoadError> {
+        if version > self.version {
+            return Err(LoadError::UnsupportedVersion(version));
+        }
+        for version in version..self.version {
+            let Some(migration) = self.migrations.get(&version) else {
+                return Err(LoadError::UnsupportedVersion(version));
+            };
+            migration(saved).map_err(|error| LoadError::Migration { version, error })?;
+        }
+        Ok(())
+    }
+
+    /// Returns the type name which a saved value of the type with the given `type_name` should be deserialized as,
+    /// if it is saved in a save file with the given `version`.
+    ///
+    /// This is the legacy type registered for the earliest version at or after `version`, if any.
+    /// See [`RegisterMigration::register_legacy_type`] for details.
+    pub fn legacy_type_name(&self, type_name: &str, version: u32) -> Option<&str> {
+        let (_, legacy_type_name) = self.legacy_types.get(type_name)?.range(version..).next()?;
+        Some(legacy_type_name)
+    }
+
+    /// Returns the given [`SaveAliases`], extended to resolve all legacy types of save files with the given `version`.
+    pub(crate) fn aliases(&self, version: u32, aliases: &SaveAliases) -> SaveAliases {
+        let mut aliases = aliases.clone();
+        for type_name in aliases.0.values_mut() {
+            if let Some(legacy_type_name) = self.legacy_type_name(type_name, version) {
+                *type_name = legacy_type_name.to_string();
+            }
+        }
+        for type_name in self.legacy_types.keys() {
+            if let Some(legacy_type_name) = self.legacy_type_name(type_name, version) {
+                aliases
+                    .0
+                    .insert(type_name.clone(), legacy_type_name.to_string());
+            }
+        }
+        aliases
+    }
+}
+
+/// Extension trait used to register the schema version of save files and its migrations with an [`App`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{RegisterMigration, SavePlugin};
+/// #[derive(Reflect, FromReflect)]
+/// struct OldHealth(u32);
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// struct Health(f32);
+///
+/// App::new()
+///     .add_plugin(SavePlugin::default())
+///     .register_type::<OldHealth>()
+///     .register_type::<Health>()
+///     .set_save_version(1)
+///     .register_migration(0, |saved| {
+///         for component in saved.components_mut() {
+///             if let Some(OldHealth(health)) = OldHealth::from_reflect(&**component) {
+///                 *component = Box::new(Health(health as f32));
+///             }
+///         }
+///         Ok(())
+///     });
+/// ```
+///
+/// If a type is changed without changing its name, its previous layout must be registered as a legacy type:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{RegisterMigration, SavePlugin};
+/// mod legacy {
+///     # use bevy::prelude::*;
+///     #[derive(Default, Reflect, FromReflect)]
+///     pub struct Stamina {
+///         pub points: u32,
+///     }
+/// }
+///
+/// #[derive(Component, Default, Reflect)]
+/// #[reflect(Component)]
+/// struct Stamina {
+///     current: u32,
+/// }
+///
+/// App::new()
+///     .add_plugin(SavePlugin::default())
+///     .register_type::<Stamina>()
+///     .set_save_version(1)
+///     .register_legacy_type::<legacy::Stamina>(0, std::any::type_name::<Stamina>())
+///     .register_migration(0, |saved| {
+///         for component in saved.components_mut() {
+///             if let Some(legacy::Stamina { points }) = legacy::Stamina::from_reflect(&**component) {
+///                 *component = Box::new(Stamina { current: points });
+///             }
+///         }
+///         Ok(())
+///     });
+/// ```
+pub trait RegisterMigration {
+    /// Sets the current schema version of save files.
+    fn set_save_version(self, version: u32) -> Self;
+
+    /// Registers a migration which upgrades a [`SavedWorld`] from the given `version` to `version + 1`.
+    fn register_migration(
+        self,
+        version: u32,
+        migration: impl Fn(&mut SavedWorld) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self;
+
+    /// Registers `T` as the layout of the type with the given `type_name` in save files with the given `version`
+    /// or earlier.
+    ///
+    /// During load of such a save file, any saved value of the type with the given `type_name` is deserialized
+    /// as `T` instead, so a migration may replace it with its current equivalent. `T` is registered in the type
+    /// registry, and it should not be a component or resource itself.
+    fn register_legacy_type<T: GetTypeRegistration>(
+        self,
+        version: u32,
+        type_name: impl Into<String>,
+    ) -> Self;
+}
+
+impl RegisterMigration for &mut App {
+    fn set_save_version(self, version: u32) -> Self {
+        self.world
+            .get_resource_or_insert_with(SaveMigrations::default)
+            .version = version;
+        self
+    }
+
+    fn register_migration(
+        self,
+        version: u32,
+        migration: impl Fn(&mut SavedWorld) -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+            + Send
+            + Sync
+            + 'static,
+    ) -> Self {
+        self.world
+            .get_resource_or_insert_with(SaveMigrations::default)
+            .migrations
+            .insert(version, Arc::new(migration));
+        self
+    }
+
+    fn register_legacy_type<T: GetTypeRegistration>(
+        self,
+        version: u32,
+        type_name: impl Into<String>,
+    ) -> Self {
+        self.register_type::<T>()
+            .world
+            .get_resource_or_insert_with(SaveMigrations::default)
+            .legacy_types
+            .entry(type_name.into())
+            .or_default()
+            .insert(version, std::any::type_name::<T>().to_string());
+        self
+    }
+}
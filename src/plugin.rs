@@ -1,7 +1,88 @@
+use std::any::TypeId;
+
+use bevy::utils::HashMap;
+
 use super::*;
 
 /// A [`Plugin`] which adds the [`SaveStage`] and any required systems for saving and loading the [`World`].
-pub struct SavePlugin;
+///
+/// # Usage
+///
+/// The plugin may be configured using its [`SaveSettings`], which are inserted into the app as a resource:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{Compression, SavePlugin};
+/// App::new().add_plugin(SavePlugin::default().with_compression(Compression::None));
+/// ```
+#[derive(Default)]
+pub struct SavePlugin {
+    /// Default settings of all save and load requests.
+    pub settings: SaveSettings,
+}
+
+impl SavePlugin {
+    /// Sets the default [`Compression`] of all save requests.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        self.settings.compression = compression;
+        self
+    }
+
+    /// Sets the [`BackupPolicy`] of all save requests.
+    pub fn with_backups(mut self, backups: BackupPolicy) -> Self {
+        self.settings.backups = backups;
+        self
+    }
+
+    /// Sets the [`DanglingPolicy`] used to update components registered using [`RegisterLoaded`],
+    /// [`ReflectFromLoaded`], or [`ReflectMapEntities`](bevy::ecs::reflect::ReflectMapEntities).
+    pub fn with_dangling_policy(mut self, dangling: DanglingPolicy) -> Self {
+        self.settings.dangling = dangling;
+        self
+    }
+
+    /// Sets the [`SaveFilter`] of all save requests.
+    pub fn with_filter(mut self, filter: SaveFilter) -> Self {
+        self.settings.filter = filter;
+        self
+    }
+
+    /// Sets the [`SaveFilter`] of all save requests for the save group `G`. See [`SaveGroup`] for details.
+    ///
+    /// This filter is applied in addition to the filter set using [`SavePlugin::with_filter`].
+    pub fn with_group_filter<G: 'static>(mut self, filter: SaveFilter) -> Self {
+        self.settings
+            .group_filters
+            .insert(TypeId::of::<G>(), filter);
+        self
+    }
+}
+
+/// A [`Resource`] which contains the default settings of all save and load requests.
+///
+/// This resource is inserted by [`SavePlugin`], and may be modified at any time.
+#[derive(Resource, Clone, Debug, Default)]
+pub struct SaveSettings {
+    /// Compression of any [`Request::Save`] which does not specify its own compression.
+    pub compression: Compression,
+    /// Backup policy of all save requests.
+    pub backups: BackupPolicy,
+    /// Policy used to handle dangling entity references of loaded components.
+    pub dangling: DanglingPolicy,
+    /// Filter of components saved by all save requests.
+    pub filter: SaveFilter,
+    /// Filter of components saved by all save requests for a save group, keyed by the [`TypeId`] of the group.
+    pub group_filters: HashMap<TypeId, SaveFilter>,
+}
+
+impl SaveSettings {
+    /// Returns the [`SaveFilter`] of a save request for the given save `group`, excluding any request filter.
+    pub fn filter(&self, group: Option<SaveGroupFilter>) -> SaveFilter {
+        match group.and_then(|group| self.group_filters.get(&group.type_id())) {
+            Some(group_filter) => self.filter.clone().and(group_filter),
+            None => self.filter.clone(),
+        }
+    }
+}
 
 impl Plugin for SavePlugin {
     fn build(&self, app: &mut App) {
@@ -18,8 +99,25 @@ impl Plugin for SavePlugin {
         .add_stage_after(
             SaveStage::Load,
             SaveStage::PostLoad,
-            SystemStage::parallel().with_run_criteria(should_load),
+            SystemStage::parallel().with_run_criteria(should_post_load),
         )
-        .add_system_to_stage(SaveStage::PostLoad, finish_load);
+        .add_system_to_stage(SaveStage::PostLoad, reflect_loaded)
+        .add_system_to_stage(SaveStage::PostLoad, finish_load)
+        .add_system_to_stage(CoreStage::Last, poll_saves)
+        .add_system_to_stage(CoreStage::PostUpdate, update_save_ids)
+        .add_system_to_stage(CoreStage::PostUpdate, update_group_save_ids)
+        .insert_resource(self.settings.clone())
+        .init_resource::<RequestQueue>()
+        .init_resource::<PendingSaves>()
+        .init_resource::<SaveIdIndex>()
+        .register_saved_resource_with::<SaveIdIndex>(LoadResourceMode::Apply)
+        .register_type::<SaveId>()
+        .register_type::<SaveRef>()
+        .register_type::<Option<SaveId>>()
+        .add_event::<SavedEvent>()
+        .add_event::<SaveFailedEvent>()
+        .add_event::<LoadedEvent>()
+        .add_event::<LoadFailedEvent>()
+        .add_event::<DanglingEntitiesEvent>();
     }
 }
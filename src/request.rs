@@ -0,0 +1,383 @@
+use bevy::ecs::world::EntityMut;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use super::*;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SaveMode {
+    /// Save entities with a [`Save`] component.
+    Filtered,
+    /// Save all entities.
+    Dump,
+}
+
+/// Defines how a [`Request::Load`] is written into the [`World`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoadMode {
+    /// Despawn all entities with a [`Save`] or [`Unload`] component, and replace all saved resources
+    /// before the saved entities are spawned.
+    #[default]
+    Replace,
+    /// Spawn the saved entities alongside any existing entities. Saved resources are not loaded.
+    ///
+    /// See [`LoadWorld::load_additive`] for details.
+    Additive,
+}
+
+/// A [`Component`] which is inserted into every entity spawned by a [`Request::Load`].
+///
+/// See [`LoadWorld::load_additive_with_marker`] for details.
+#[derive(Clone)]
+pub struct LoadMarker(Arc<dyn Fn(&mut EntityMut) + Send + Sync>);
+
+impl LoadMarker {
+    /// Returns a new [`LoadMarker`] which inserts a clone of the given `marker` component.
+    pub fn new<M: Component + Clone>(marker: M) -> Self {
+        Self(Arc::new(move |entity| {
+            entity.insert(marker.clone());
+        }))
+    }
+
+    /// Inserts the marker component into the given `entity`.
+    pub fn insert(&self, entity: &mut EntityMut) {
+        (self.0)(entity);
+    }
+}
+
+impl fmt::Debug for LoadMarker {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LoadMarker")
+    }
+}
+
+/// A save or load request, queued using [`SaveWorld`], [`LoadWorld`] or [`QueueRequest`].
+///
+/// # Usage
+///
+/// Requests are created using [`Request::save`], [`Request::dump`] or [`Request::load`], and configured
+/// using their builder methods:
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{Compression, QueueRequest, Request};
+/// fn save(mut commands: Commands) {
+///     commands.queue_request(Request::save("world.sav").with_compression(Compression::None));
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub enum Request {
+    #[non_exhaustive]
+    Save {
+        path: PathBuf,
+        mode: SaveMode,
+        /// If `true`, the file is serialized and written in the background. See [`PendingSaves`].
+        background: bool,
+        /// Serialization format of the file.
+        format: SaveFormat,
+        /// Compression of the file. If `None`, the default compression from [`SaveSettings`] is used.
+        compression: Option<Compression>,
+        /// If set, only components allowed by this filter (and any filters in [`SaveSettings`]) are saved.
+        filter: Option<SaveFilter>,
+        /// If set, only entities in this save group are saved. See [`SaveGroup`].
+        group: Option<SaveGroupFilter>,
+        /// If set, the file is not written if the checksum of the saved world matches this checksum.
+        /// See [`Request::skip_if_unchanged`].
+        unchanged: Option<u32>,
+    },
+    #[non_exhaustive]
+    Load {
+        path: PathBuf,
+        /// If `true`, the file is read and deserialized in the background. See [`LoadProgress`].
+        background: bool,
+        /// If `true`, the newest valid backup is loaded if the file fails validation.
+        /// See [`LoadWorld::load_latest_valid`].
+        fallback: bool,
+        /// Whether existing entities are unloaded before the saved entities are spawned.
+        mode: LoadMode,
+        /// If set, this marker is inserted into every loaded entity.
+        marker: Option<LoadMarker>,
+        /// If set, only entities in this save group are unloaded. See [`SaveGroup`].
+        group: Option<SaveGroupFilter>,
+    },
+}
+
+impl Request {
+    /// Returns a new [`Request::Save`] into the given `path`, with [`SaveMode::Filtered`] and [`SaveFormat::Ron`].
+    pub fn save(path: impl Into<PathBuf>) -> Self {
+        Self::Save {
+            path: path.into(),
+            mode: SaveMode::Filtered,
+            background: false,
+            format: SaveFormat::Ron,
+            compression: None,
+            filter: None,
+            group: None,
+            unchanged: None,
+        }
+    }
+
+    /// Returns a new [`Request::Save`] into the given `path`, with [`SaveMode::Dump`]. See [`SaveWorld::dump`].
+    pub fn dump(path: impl Into<PathBuf>) -> Self {
+        let mut request = Self::save(path);
+        if let Self::Save { mode, .. } = &mut request {
+            *mode = SaveMode::Dump;
+        }
+        request
+    }
+
+    /// Returns a new [`Request::Load`] from the given `path`, with [`LoadMode::Replace`].
+    pub fn load(path: impl Into<PathBuf>) -> Self {
+        Self::Load {
+            path: path.into(),
+            background: false,
+            fallback: false,
+            mode: LoadMode::Replace,
+            marker: None,
+            group: None,
+        }
+    }
+
+    /// Processes this request in the background.
+    ///
+    /// See [`SaveWorld::save_in_background`] and [`LoadWorld::load_in_background`] for details.
+    pub fn in_background(mut self) -> Self {
+        match &mut self {
+            Self::Save { background, .. } | Self::Load { background, .. } => *background = true,
+        }
+        self
+    }
+
+    /// Scopes this request to the save group `G`. See [`SaveGroup`] for details.
+    pub fn with_group<G: 'static>(mut self) -> Self {
+        match &mut self {
+            Self::Save { group, .. } | Self::Load { group, .. } => {
+                *group = Some(SaveGroupFilter::of::<G>());
+            }
+        }
+        self
+    }
+
+    /// Sets the [`SaveFormat`] of this save request. This has no effect on load requests.
+    pub fn with_format(mut self, format: SaveFormat) -> Self {
+        if let Self::Save { format: f, .. } = &mut self {
+            *f = format;
+        }
+        self
+    }
+
+    /// Sets the [`Compression`] of this save request, instead of the default compression from [`SaveSettings`].
+    /// This has no effect on load requests.
+    pub fn with_compression(mut self, compression: Compression) -> Self {
+        if let Self::Save { compression: c, .. } = &mut self {
+            *c = Some(compression);
+        }
+        self
+    }
+
+    /// Sets the [`SaveFilter`] of this save request. This has no effect on load requests.
+    pub fn with_filter(mut self, filter: SaveFilter) -> Self {
+        if let Self::Save { filter: f, .. } = &mut self {
+            *f = Some(filter);
+        }
+        self
+    }
+
+    /// Skips writing the file of this save request if the saved world has not changed since a previous save with
+    /// the given `checksum` (see [`SavedEvent::checksum`]). This has no effect on load requests.
+    ///
+    /// The world is still saved and serialized to compute its checksum, so this only avoids writing the file.
+    /// A [`SavedEvent`] is sent either way, with [`SavedEvent::skipped`] set if the file was not written.
+    pub fn skip_if_unchanged(mut self, checksum: u32) -> Self {
+        if let Self::Save { unchanged, .. } = &mut self {
+            *unchanged = Some(checksum);
+        }
+        self
+    }
+
+    /// Sets the [`LoadMode`] of this load request. This has no effect on save requests.
+    pub fn with_load_mode(mut self, mode: LoadMode) -> Self {
+        if let Self::Load { mode: m, .. } = &mut self {
+            *m = mode;
+        }
+        self
+    }
+
+    /// Falls back to the newest valid backup if the file fails validation. This has no effect on save requests.
+    ///
+    /// See [`LoadWorld::load_latest_valid`] for details.
+    pub fn with_fallback(mut self) -> Self {
+        if let Self::Load { fallback, .. } = &mut self {
+            *fallback = true;
+        }
+        self
+    }
+
+    /// Inserts the given `marker` component into every loaded entity. This has no effect on save requests.
+    ///
+    /// See [`LoadWorld::load_additive_with_marker`] for details.
+    pub fn with_marker<M: Component + Clone>(mut self, marker: M) -> Self {
+        if let Self::Load { marker: m, .. } = &mut self {
+            *m = Some(LoadMarker::new(marker));
+        }
+        self
+    }
+
+    /// Returns the path of the file to save or load.
+    pub fn path(&self) -> &Path {
+        match self {
+            Self::Save { path, .. } | Self::Load { path, .. } => path,
+        }
+    }
+
+    pub(crate) fn should_save(&self) -> bool {
+        matches!(self, Self::Save { .. })
+    }
+
+    pub(crate) fn should_load(&self) -> bool {
+        matches!(self, Self::Load { .. })
+    }
+}
+
+/// A unique identifier of a queued [`Request`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct RequestId(u64);
+
+impl RequestId {
+    pub(crate) fn next() -> Self {
+        static NEXT: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
+/// A [`Resource`] which contains all pending save and load requests.
+///
+/// # Usage
+///
+/// Requests are processed in the order they are queued, with the following semantics:
+/// - All save requests are processed during [`SaveStage::Save`], at the end of the frame in which they are queued.
+/// - Only one load request is processed during each [`SaveStage::Load`], at the start of the next frame.
+/// - A load request is never processed before any save request queued before it.
+///
+/// This means that within a frame, all saves happen before any load. Any save requested in the same frame as
+/// a load always saves the world as it was before the load.
+///
+/// Requests may be inspected or cancelled until they are processed. A request which is being loaded in
+/// the background (see [`LoadWorld::load_in_background`]) remains in the queue until the load is complete,
+/// and may still be cancelled.
+#[derive(Resource, Default)]
+pub struct RequestQueue(VecDeque<(RequestId, Request)>);
+
+impl RequestQueue {
+    /// Adds a new [`Request`] to the back of the queue and returns its [`RequestId`].
+    pub fn push(&mut self, request: Request) -> RequestId {
+        let id = RequestId::next();
+        self.0.push_back((id, request));
+        id
+    }
+
+    /// Queues a new [`Request`] with a previously allocated [`RequestId`].
+    pub(crate) fn push_with_id(&mut self, id: RequestId, request: Request) {
+        self.0.push_back((id, request));
+    }
+
+    /// Returns `true` if there are no pending requests.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of pending requests.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns an iterator over all pending requests, in the order they are processed.
+    pub fn iter(&self) -> impl Iterator<Item = (RequestId, &Request)> {
+        self.0.iter().map(|(id, request)| (*id, request))
+    }
+
+    /// Returns the pending [`Request`] with the given [`RequestId`], if it exists.
+    pub fn get(&self, id: RequestId) -> Option<&Request> {
+        self.iter()
+            .find(|(request_id, _)| *request_id == id)
+            .map(|(_, request)| request)
+    }
+
+    /// Returns `true` if there is a pending [`Request::Save`].
+    pub fn has_save(&self) -> bool {
+        self.0.iter().any(|(_, request)| request.should_save())
+    }
+
+    /// Returns `true` if there is a pending [`Request::Load`].
+    pub fn has_load(&self) -> bool {
+        self.0.iter().any(|(_, request)| request.should_load())
+    }
+
+    /// Cancels the pending [`Request`] with the given [`RequestId`] and returns it, if it exists.
+    pub fn cancel(&mut self, id: RequestId) -> Option<Request> {
+        let index = self
+            .0
+            .iter()
+            .position(|(request_id, _)| *request_id == id)?;
+        self.0.remove(index).map(|(_, request)| request)
+    }
+
+    /// Cancels all pending requests for which the given predicate returns `true`.
+    pub fn cancel_where(&mut self, mut predicate: impl FnMut(&Request) -> bool) {
+        self.0.retain(|(_, request)| !predicate(request));
+    }
+
+    /// Cancels all pending requests.
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    /// Removes and returns the first pending [`Request::Save`].
+    pub(crate) fn pop_save(&mut self) -> Option<(RequestId, Request)> {
+        let index = self
+            .0
+            .iter()
+            .position(|(_, request)| request.should_save())?;
+        self.0.remove(index)
+    }
+
+    /// Returns the first pending [`Request::Load`].
+    pub(crate) fn first_load(&self) -> Option<(RequestId, &Request)> {
+        self.iter().find(|(_, request)| request.should_load())
+    }
+
+    /// Returns `true` if there is a pending [`Request::Save`] queued before the request with the given [`RequestId`].
+    pub(crate) fn has_save_before(&self, id: RequestId) -> bool {
+        self.iter()
+            .take_while(|(request_id, _)| *request_id != id)
+            .any(|(_, request)| request.should_save())
+    }
+}
+
+/// Trait used to queue a [`Request`] into the [`RequestQueue`] of a [`World`].
+///
+/// This trait is implemented for `&mut World` and `&mut Commands`. Any type which implements this
+/// trait also implements [`SaveWorld`] and [`LoadWorld`].
+pub trait QueueRequest {
+    /// Adds the given [`Request`] to the back of the [`RequestQueue`] and returns its [`RequestId`].
+    fn queue_request(self, request: Request) -> RequestId;
+}
+
+impl QueueRequest for &mut World {
+    fn queue_request(self, request: Request) -> RequestId {
+        self.get_resource_or_insert_with(RequestQueue::default)
+            .push(request)
+    }
+}
+
+impl QueueRequest for &mut Commands<'_, '_> {
+    fn queue_request(self, request: Request) -> RequestId {
+        let id = RequestId::next();
+        self.add(move |world: &mut World| {
+            world
+                .get_resource_or_insert_with(RequestQueue::default)
+                .push_with_id(id, request);
+        });
+        id
+    }
+}
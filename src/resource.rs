@@ -0,0 +1,139 @@
+use std::any::TypeId;
+
+use bevy::ecs::reflect::ReflectResource;
+use bevy::reflect::{FromType, GetTypeRegistration};
+
+use super::*;
+
+/// Defines how a saved [`Resource`] is restored during [`SaveStage::Load`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LoadResourceMode {
+    /// The loaded resource is inserted into the [`World`], replacing any existing instance of it.
+    ///
+    /// Any fields which are not reflected are initialized using [`FromWorld`].
+    #[default]
+    Replace,
+    /// The loaded resource is applied to the existing instance in the [`World`], or inserted if it does not exist.
+    ///
+    /// Any fields which are not reflected keep their current values.
+    Apply,
+}
+
+/// A [`Resource`] which contains all resources registered using [`RegisterSavedResource`].
+#[derive(Resource, Clone, Default)]
+pub struct SavedResources(Vec<(TypeId, LoadResourceMode)>);
+
+impl SavedResources {
+    /// Returns an iterator over the [`TypeId`] of all saved resources.
+    pub fn iter(&self) -> impl Iterator<Item = TypeId> + '_ {
+        self.0.iter().map(|(type_id, _)| *type_id)
+    }
+
+    /// Returns the [`LoadResourceMode`] of the resource with the given [`TypeId`], if it is saved.
+    pub fn mode(&self, type_id: TypeId) -> Option<LoadResourceMode> {
+        self.0
+            .iter()
+            .find(|(id, _)| *id == type_id)
+            .map(|(_, mode)| *mode)
+    }
+
+    fn insert(&mut self, type_id: TypeId, mode: LoadResourceMode) {
+        if let Some((_, existing_mode)) = self.0.iter_mut().find(|(id, _)| *id == type_id) {
+            *existing_mode = mode;
+        } else {
+            self.0.push((type_id, mode));
+        }
+    }
+}
+
+/// Extension trait used to register resources which should be saved and loaded with an [`App`].
+///
+/// # Usage
+///
+/// Unlike entities, resources are not marked for save using a component. Instead, any resource
+/// registered using this trait is saved with every save request, and restored during [`SaveStage::Load`].
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{RegisterSavedResource, SavePlugin};
+/// #[derive(Resource, Default, Reflect)]
+/// #[reflect(Resource)]
+/// struct GameClock(f64);
+///
+/// App::new()
+///     .add_plugin(SavePlugin::default())
+///     .register_saved_resource::<GameClock>();
+/// ```
+pub trait RegisterSavedResource {
+    /// Registers a [`Resource`] to be saved and loaded using [`LoadResourceMode::Replace`].
+    fn register_saved_resource<R: SavedResource>(self) -> Self;
+
+    /// Registers a [`Resource`] to be saved and loaded using the given [`LoadResourceMode`].
+    fn register_saved_resource_with<R: SavedResource>(self, mode: LoadResourceMode) -> Self;
+}
+
+/// A [`Resource`] which may be registered using [`RegisterSavedResource`].
+pub trait SavedResource: Resource + Reflect + FromWorld + GetTypeRegistration {}
+
+impl<R: Resource + Reflect + FromWorld + GetTypeRegistration> SavedResource for R {}
+
+impl RegisterSavedResource for &mut App {
+    fn register_saved_resource<R: SavedResource>(self) -> Self {
+        self.register_saved_resource_with::<R>(LoadResourceMode::default())
+    }
+
+    fn register_saved_resource_with<R: SavedResource>(self, mode: LoadResourceMode) -> Self {
+        self.register_type::<R>();
+        self.world
+            .resource::<AppTypeRegistry>()
+            .write()
+            .get_mut(TypeId::of::<R>())
+            .expect("type must be registered")
+            .insert(<ReflectResource as FromType<R>>::from_type());
+        self.world
+            .get_resource_or_insert_with(SavedResources::default)
+            .insert(TypeId::of::<R>(), mode);
+        self
+    }
+}
+
+/// Returns a copy of all saved resources in the given [`World`].
+pub(crate) fn save_resources(world: &World) -> Vec<Box<dyn Reflect>> {
+    let Some(saved_resources) = world.get_resource::<SavedResources>() else {
+        return Vec::new();
+    };
+    let registry = world.resource::<AppTypeRegistry>().read();
+    saved_resources
+        .iter()
+        .filter_map(|type_id| {
+            let reflect_resource = registry.get_type_data::<ReflectResource>(type_id)?;
+            let resource = reflect_resource.reflect(world)?;
+            Some(resource.clone_value())
+        })
+        .collect()
+}
+
+/// Restores the given saved `resources` into the given [`World`].
+///
+/// All resources must be validated using [`validate_scene`] beforehand.
+pub(crate) fn load_resources(world: &mut World, resources: &[Box<dyn Reflect>]) {
+    let registry = world.resource::<AppTypeRegistry>().clone();
+    let registry = registry.read();
+    for resource in resources {
+        let registration = registry
+            .get_with_name(resource.type_name())
+            .expect("resource must be registered");
+        let reflect_resource = registration
+            .data::<ReflectResource>()
+            .expect("resource must reflect `Resource`");
+        let mode = world
+            .get_resource::<SavedResources>()
+            .and_then(|saved_resources| saved_resources.mode(registration.type_id()))
+            .unwrap_or_default();
+        match mode {
+            LoadResourceMode::Replace => reflect_resource.insert(world, &**resource),
+            LoadResourceMode::Apply => reflect_resource.apply_or_insert(world, &**resource),
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use bevy::app::AppExit;
+
 use super::*;
 
 /// A [`Component`] which indicates that its [`Entity`] should be saved.
@@ -6,46 +8,452 @@ use super::*;
 #[derive(Component, Default, Clone)]
 pub struct Save;
 
-/// A [`RunCriteria`] which returns [`ShouldRun::Yes`] if there is a save [`Request`] present; [`ShouldRun::No`] otherwise.
-pub fn should_save(request: Option<Res<Request>>) -> ShouldRun {
-    match request.map(|request| request.should_save()) {
+/// A [`RunCriteria`] which returns [`ShouldRun::Yes`] if there is a save [`Request`] queued; [`ShouldRun::No`] otherwise.
+pub fn should_save(queue: Option<Res<RequestQueue>>) -> ShouldRun {
+    match queue.map(|queue| queue.has_save()) {
         Some(true) => ShouldRun::Yes,
         _ => ShouldRun::No,
     }
 }
 
-/// A [`System`] which handles a save [`Request`].
+/// An [`Event`] sent after a save [`Request`] is completed successfully.
+#[derive(Debug)]
+pub struct SavedEvent {
+    /// ID of the save request.
+    pub id: RequestId,
+    /// Path of the saved file.
+    pub path: PathBuf,
+    /// Mode of the save request.
+    pub mode: SaveMode,
+    /// CRC32 checksum of the saved world, as serialized before compression.
+    ///
+    /// This may be passed to [`Request::skip_if_unchanged`] to skip the next save if nothing has changed.
+    pub checksum: u32,
+    /// `true` if the file was not written, because the saved world matched the checksum given to
+    /// [`Request::skip_if_unchanged`].
+    pub skipped: bool,
+}
+
+/// An [`Event`] sent after a save [`Request`] fails.
+#[derive(Debug)]
+pub struct SaveFailedEvent {
+    /// ID of the save request.
+    pub id: RequestId,
+    /// Path of the file which failed to save.
+    pub path: PathBuf,
+    /// Mode of the save request.
+    pub mode: SaveMode,
+    /// Cause of failure.
+    pub error: SaveError,
+}
+
+/// Cause of a failed save [`Request`].
+#[derive(Debug)]
+pub enum SaveError {
+    /// The [`SavedWorld`] could not be serialized.
+    Serialize(Box<dyn std::error::Error + Send + Sync>),
+    /// The requested [`SaveFormat`] is not supported, because its cargo feature is not enabled.
+    UnsupportedFormat(SaveFormat),
+    /// The requested [`Compression`] is not supported, because its cargo feature is not enabled.
+    UnsupportedCompression(Compression),
+    /// The save file could not be written.
+    Io(io::Error),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Serialize(why) => write!(f, "serialization failed: {why}"),
+            Self::UnsupportedFormat(format) => write!(f, "unsupported format: {format:?}"),
+            Self::UnsupportedCompression(compression) => {
+                write!(f, "unsupported compression: {compression:?}")
+            }
+            Self::Io(why) => write!(f, "file write failed: {why}"),
+        }
+    }
+}
+
+impl std::error::Error for SaveError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Serialize(why) => Some(why.as_ref()),
+            Self::UnsupportedFormat(_) | Self::UnsupportedCompression(_) => None,
+            Self::Io(why) => Some(why),
+        }
+    }
+}
+
+impl From<ron::Error> for SaveError {
+    fn from(why: ron::Error) -> Self {
+        Self::Serialize(why.into())
+    }
+}
+
+impl From<io::Error> for SaveError {
+    fn from(why: io::Error) -> Self {
+        Self::Io(why)
+    }
+}
+
+/// A [`System`] which handles all queued save requests, in order.
+///
+/// A [`SavedEvent`] is sent for each request which succeeds; a [`SaveFailedEvent`] is sent otherwise.
+///
+/// If the request should be saved in the background, the [`SavedWorld`] is extracted immediately,
+/// but it is serialized and written into the file on the [`IoTaskPool`]. See [`PendingSaves`] for details.
 pub fn save(world: &mut World) {
-    if let Some(Request::Save { path, mode }) = world.remove_resource::<Request>() {
-        let entities: Vec<Entity> = match mode {
-            SaveMode::Filtered => world
+    while let Some((id, request)) = world
+        .get_resource_mut::<RequestQueue>()
+        .and_then(|mut queue| queue.pop_save())
+    {
+        let Request::Save {
+            path,
+            mode,
+            background,
+            format,
+            compression,
+            filter,
+            group,
+            unchanged,
+        } = request
+        else {
+            unreachable!("`pop_save` must only return save requests");
+        };
+
+        // Avoid overlapping writes into the same file:
+        wait_for_save(world, &path);
+
+        let entities: Vec<Entity> = match (mode, group) {
+            (SaveMode::Filtered, Some(group)) => {
+                index_group_save_ids(world, group);
+                group.saved_entities(world)
+            }
+            (SaveMode::Filtered, None) => world
                 .query_filtered::<Entity, With<Save>>()
                 .iter(world)
                 .collect(),
-            SaveMode::Dump => world.iter_entities().collect(),
+            (SaveMode::Dump, _) => world.iter_entities().collect(),
+        };
+        let settings = world
+            .get_resource::<SaveSettings>()
+            .cloned()
+            .unwrap_or_default();
+        let mut component_filter = settings.filter(group);
+        if let Some(filter) = &filter {
+            component_filter = component_filter.and(filter);
+        }
+        let mut saved = save_world_filtered(world, entities, &component_filter);
+        if group.is_some() {
+            // Saved resources are not part of any group:
+            saved.resources.clear();
+        }
+        let registry = world.resource::<AppTypeRegistry>().clone();
+        let metadata = match save_metadata(world) {
+            Ok(metadata) => metadata,
+            Err(error) => {
+                send_save_result(world, id, path, mode, Err(error));
+                continue;
+            }
+        };
+        let header = SaveHeader {
+            format,
+            compression: compression.unwrap_or(settings.compression),
+            metadata,
+            version: world
+                .get_resource::<SaveMigrations>()
+                .map(SaveMigrations::version)
+                .unwrap_or_default(),
         };
+        let backups = settings.backups;
 
-        let scene = save_world(world, entities);
-        match scene.serialize_ron(world.resource::<AppTypeRegistry>()) {
-            Ok(serialized_scene) => match File::create(&path) {
-                Ok(mut file) => match file.write_all(serialized_scene.as_bytes()) {
-                    Ok(()) => info!("save successful: {path:?}"),
-                    Err(why) => error!("save failed: {why:?}"),
-                },
-                Err(why) => {
-                    error!("file creation failed: {why:?}");
-                }
-            },
-            Err(why) => {
-                error!("serialization failed: {why:?}");
+        if background {
+            let task_path = path.clone();
+            let task = IoTaskPool::get().spawn(async move {
+                write_saved_world(&task_path, &saved, &registry, header, backups, unchanged)
+            });
+            world
+                .get_resource_or_insert_with(PendingSaves::default)
+                .0
+                .push(PendingSave {
+                    id,
+                    path,
+                    mode,
+                    task,
+                });
+        } else {
+            let result = write_saved_world(&path, &saved, &registry, header, backups, unchanged);
+            send_save_result(world, id, path, mode, result);
+        }
+    }
+    if is_exiting(world) {
+        // Saves started in the same frame as `AppExit` are never polled during `CoreStage::Last`:
+        poll_saves(world);
+    }
+}
+
+/// Serializes and compresses the given [`SavedWorld`] as described by the given [`SaveHeader`],
+/// and writes it into a file at `path` along with its checksum.
+///
+/// Any existing file at `path` is backed up according to the given [`BackupPolicy`] before it is replaced.
+///
+/// Returns the checksum of the serialized world, and whether the file was skipped because this checksum
+/// matches the given `unchanged` checksum. See [`Request::skip_if_unchanged`].
+fn write_saved_world(
+    path: &Path,
+    saved: &SavedWorld,
+    registry: &AppTypeRegistry,
+    header: SaveHeader,
+    backups: BackupPolicy,
+    unchanged: Option<u32>,
+) -> Result<(u32, bool), SaveError> {
+    let body = header.format.serialize(saved, registry)?;
+    let checksum = crc32fast::hash(&body);
+    if unchanged == Some(checksum) {
+        return Ok((checksum, true));
+    }
+    let body = header.compression.compress(body)?;
+    let header = header.to_bytes(&body)?;
+    write_atomic_with_backups(path, backups, |file| {
+        file.write_all(&header)?;
+        file.write_all(&body)
+    })?;
+    Ok((checksum, false))
+}
+
+fn send_save_result(
+    world: &mut World,
+    id: RequestId,
+    path: PathBuf,
+    mode: SaveMode,
+    result: Result<(u32, bool), SaveError>,
+) {
+    match result {
+        Ok((checksum, skipped)) => {
+            if skipped {
+                info!("save skipped, nothing changed: {path:?}");
+            } else {
+                info!("save successful: {path:?}");
             }
+            world.send_event(SavedEvent {
+                id,
+                path,
+                mode,
+                checksum,
+                skipped,
+            });
         }
+        Err(error) => {
+            error!("save failed: {error}");
+            world.send_event(SaveFailedEvent {
+                id,
+                path,
+                mode,
+                error,
+            });
+        }
+    }
+}
+
+/// A [`Resource`] which contains all background saves which are still in progress.
+///
+/// # Usage
+///
+/// Background saves are polled every frame during [`CoreStage::Last`]. A [`SavedEvent`] or [`SaveFailedEvent`]
+/// is sent once the save is complete, same as any other save request.
+///
+/// Only one save may write into any given file at a time. If a new save or load [`Request`] is made for a file
+/// which is still being saved in the background, the new request blocks until the pending save is complete.
+/// All pending saves are also completed before the app exits.
+#[derive(Resource, Default)]
+pub struct PendingSaves(Vec<PendingSave>);
+
+struct PendingSave {
+    id: RequestId,
+    path: PathBuf,
+    mode: SaveMode,
+    task: Task<Result<(u32, bool), SaveError>>,
+}
+
+impl PendingSaves {
+    /// Returns `true` if there are no background saves in progress.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the number of background saves in progress.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if a file at the given `path` is being saved in the background.
+    pub fn contains(&self, path: impl AsRef<Path>) -> bool {
+        let path = path.as_ref();
+        self.0.iter().any(|pending| pending.path == path)
     }
 }
 
-/// Saves the `entities` within the given [`World`] and returns it as a serializable [`DynamicScene`].
-pub fn save_world(world: &World, entities: impl IntoIterator<Item = Entity>) -> DynamicScene {
+/// Blocks until any background save into a file at the given `path` is complete.
+pub(crate) fn wait_for_save(world: &mut World, path: &Path) {
+    let Some(mut pending_saves) = world.get_resource_mut::<PendingSaves>() else {
+        return;
+    };
+    let Some(index) = pending_saves
+        .0
+        .iter()
+        .position(|pending| pending.path == path)
+    else {
+        return;
+    };
+    let PendingSave {
+        id,
+        path,
+        mode,
+        task,
+    } = pending_saves.0.remove(index);
+    let result = future::block_on(task);
+    send_save_result(world, id, path, mode, result);
+}
+
+/// A [`System`] which polls all [`PendingSaves`] and sends an event for each completed save.
+///
+/// If the app is exiting, this system blocks until all pending saves are complete.
+pub fn poll_saves(world: &mut World) {
+    let exiting = is_exiting(world);
+    let Some(mut pending_saves) = world.get_resource_mut::<PendingSaves>() else {
+        return;
+    };
+    let (completed, pending): (Vec<_>, Vec<_>) = std::mem::take(&mut pending_saves.0)
+        .into_iter()
+        .partition(|pending| exiting || pending.task.is_finished());
+    pending_saves.0 = pending;
+    for PendingSave {
+        id,
+        path,
+        mode,
+        task,
+    } in completed
+    {
+        let result = future::block_on(task);
+        send_save_result(world, id, path, mode, result);
+    }
+}
+
+/// Returns `true` if an [`AppExit`] event has been sent.
+fn is_exiting(world: &World) -> bool {
+    matches!(world.get_resource::<Events<AppExit>>(), Some(events) if !events.is_empty())
+}
+
+/// Saves the `entities` within the given [`World`] and returns them as a serializable [`SavedWorld`].
+///
+/// Any resources registered using [`RegisterSavedResource`] are also saved.
+/// Components marked with [`ReflectSaveSkip`] are not saved.
+pub fn save_world(world: &World, entities: impl IntoIterator<Item = Entity>) -> SavedWorld {
+    save_world_filtered(world, entities, &SaveFilter::default())
+}
+
+/// Saves the `entities` within the given [`World`] and returns them as a serializable [`SavedWorld`].
+///
+/// This is the same as [`save_world`], except that only components allowed by the given [`SaveFilter`] are saved.
+pub fn save_world_filtered(
+    world: &World,
+    entities: impl IntoIterator<Item = Entity>,
+    filter: &SaveFilter,
+) -> SavedWorld {
+    let entities: Vec<Entity> = entities.into_iter().collect();
+    let generations = entities
+        .iter()
+        .filter(|entity| entity.generation() != 0)
+        .map(|entity| (entity.index(), entity.generation()))
+        .collect();
     let mut scene_builder = DynamicSceneBuilder::from_world(world);
     scene_builder.extract_entities(entities.into_iter());
-    scene_builder.build()
+    let mut scene = scene_builder.build();
+    let registry = world.resource::<AppTypeRegistry>().read();
+    for entity in &mut scene.entities {
+        entity.components.retain(|component| {
+            // Extracted components are dynamic clones, so they must be identified by name:
+            match registry.get_with_name(component.type_name()) {
+                Some(registration) => {
+                    registration.data::<ReflectSaveSkip>().is_none()
+                        && filter.is_allowed(registration.type_id())
+                }
+                None => true,
+            }
+        });
+    }
+    SavedWorld {
+        resources: save_resources(world),
+        scene,
+        generations,
+    }
+}
+
+/// Writes a file at the given `path` atomically.
+///
+/// The data is first written into a temporary file next to `path` (i.e. `world.ron.tmp`), which is
+/// flushed and synced to disk before it is renamed to replace `path`. This ensures that a crash or
+/// power loss during save never leaves a partially written file at `path`.
+///
+/// If `write` or any file operation fails, the temporary file is removed and any existing file
+/// at `path` is left untouched.
+pub fn write_atomic(
+    path: impl AsRef<Path>,
+    write: impl FnOnce(&mut File) -> io::Result<()>,
+) -> io::Result<()> {
+    write_atomic_with_backups(path.as_ref(), BackupPolicy::None, write)
+}
+
+/// Writes a file at the given `path` atomically, like [`write_atomic`].
+///
+/// Any existing file at `path` is backed up according to the given [`BackupPolicy`] only after the temporary
+/// file is fully written, so existing backups are left untouched if the write fails.
+pub(crate) fn write_atomic_with_backups(
+    path: &Path,
+    backups: BackupPolicy,
+    write: impl FnOnce(&mut File) -> io::Result<()>,
+) -> io::Result<()> {
+    let temp_path = temp_path(path)?;
+    let result = File::create(&temp_path).and_then(|mut file| {
+        write(&mut file)?;
+        file.flush()?;
+        file.sync_all()
+    });
+    let result = result
+        .and_then(|()| backups.rotate(path))
+        .and_then(|()| fs::rename(&temp_path, path));
+    if let Err(why) = result {
+        // Best effort; the temporary file may not exist if it was never created.
+        let _ = fs::remove_file(&temp_path);
+        return Err(why);
+    }
+    sync_parent_dir(path);
+    Ok(())
 }
+
+/// Returns the path of the temporary file used by [`write_atomic`] for the given `path`.
+fn temp_path(path: &Path) -> io::Result<PathBuf> {
+    let Some(file_name) = path.file_name() else {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!("invalid save path: {path:?}"),
+        ));
+    };
+    let mut temp_file_name = file_name.to_os_string();
+    temp_file_name.push(".tmp");
+    Ok(path.with_file_name(temp_file_name))
+}
+
+/// Syncs the parent directory of the given `path` so that a rename into it is durable.
+#[cfg(unix)]
+fn sync_parent_dir(path: &Path) {
+    let parent = match path.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => parent,
+        _ => Path::new("."),
+    };
+    if let Err(why) = File::open(parent).and_then(|dir| dir.sync_all()) {
+        warn!("directory sync failed: {why:?}");
+    }
+}
+
+#[cfg(not(unix))]
+fn sync_parent_dir(_path: &Path) {}
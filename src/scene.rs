@@ -0,0 +1,335 @@
+use bevy::reflect::serde::TypedReflectDeserializer;
+use bevy::reflect::{TypeRegistryArc, TypeRegistryInternal as TypeRegistry};
+use bevy::scene::serde::{ComponentsSerializer, EntitiesSerializer};
+use bevy::scene::DynamicEntity;
+use bevy::utils::HashSet;
+use serde::de::{self, DeserializeSeed, MapAccess, SeqAccess, Visitor};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+use super::*;
+
+const SAVED_WORLD_STRUCT: &str = "Scene";
+const SAVED_WORLD_RESOURCES: &str = "resources";
+const SAVED_WORLD_ENTITIES: &str = "entities";
+const SAVED_WORLD_GENERATIONS: &str = "generations";
+const SAVED_ENTITY_STRUCT: &str = "Entity";
+const SAVED_ENTITY_COMPONENTS: &str = "components";
+
+/// A serializable collection of saved resources and entities.
+///
+/// This is the contents of a save file. It is created using [`save_world`] and loaded using [`load_world`].
+#[derive(Default)]
+pub struct SavedWorld {
+    /// Saved resources. See [`RegisterSavedResource`] for details.
+    pub resources: Vec<Box<dyn Reflect>>,
+    /// Saved entities and their components.
+    pub scene: DynamicScene,
+    /// Generation of each saved entity, keyed by its index. Entities with generation `0` are omitted.
+    pub generations: BTreeMap<u32, u32>,
+}
+
+impl SavedWorld {
+    /// Returns the saved entities and their components.
+    pub fn entities(&self) -> &[DynamicEntity] {
+        &self.scene.entities
+    }
+
+    /// Returns the saved [`Entity`] (i.e. its index and generation) of the given saved entity.
+    pub fn entity(&self, entity: &DynamicEntity) -> Entity {
+        let generation = self.generations.get(&entity.entity).copied().unwrap_or(0);
+        Entity::from_bits((generation as u64) << 32 | entity.entity as u64)
+    }
+
+    /// Returns an iterator over all saved components of all saved entities.
+    pub fn components_mut(&mut self) -> impl Iterator<Item = &mut Box<dyn Reflect>> {
+        self.scene
+            .entities
+            .iter_mut()
+            .flat_map(|entity| entity.components.iter_mut())
+    }
+}
+
+/// Serializes a [`SavedWorld`].
+///
+/// The resulting structure is compatible with a serialized [`DynamicScene`], with additional
+/// `resources` and `generations` fields.
+pub struct SavedWorldSerializer<'a> {
+    pub saved: &'a SavedWorld,
+    pub registry: &'a TypeRegistryArc,
+}
+
+impl<'a> SavedWorldSerializer<'a> {
+    pub fn new(saved: &'a SavedWorld, registry: &'a TypeRegistryArc) -> Self {
+        Self { saved, registry }
+    }
+}
+
+impl Serialize for SavedWorldSerializer<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut state = serializer.serialize_struct(SAVED_WORLD_STRUCT, 3)?;
+        state.serialize_field(
+            SAVED_WORLD_RESOURCES,
+            &ComponentsSerializer {
+                components: &self.saved.resources,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(
+            SAVED_WORLD_ENTITIES,
+            &EntitiesSerializer {
+                entities: &self.saved.scene.entities,
+                registry: self.registry,
+            },
+        )?;
+        state.serialize_field(SAVED_WORLD_GENERATIONS, &self.saved.generations)?;
+        state.end()
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SavedWorldField {
+    Resources,
+    Entities,
+    Generations,
+}
+
+/// Deserializes a [`SavedWorld`].
+///
+/// Files which were saved as a plain [`DynamicScene`] (i.e. without any `resources`) are also accepted.
+/// Files saved without `generations` are accepted as long as the format stores the length of each
+/// struct (i.e. RON and MessagePack); all of their entities are assumed to have generation `0`.
+///
+/// The type name of each saved component and resource is resolved using the given [`SaveAliases`] before it is
+/// looked up in the type registry.
+pub struct SavedWorldDeserializer<'a> {
+    pub type_registry: &'a TypeRegistry,
+    pub aliases: &'a SaveAliases,
+}
+
+impl<'de> DeserializeSeed<'de> for SavedWorldDeserializer<'_> {
+    type Value = SavedWorld;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct(
+            SAVED_WORLD_STRUCT,
+            &[
+                SAVED_WORLD_RESOURCES,
+                SAVED_WORLD_ENTITIES,
+                SAVED_WORLD_GENERATIONS,
+            ],
+            SavedWorldVisitor {
+                type_registry: self.type_registry,
+                aliases: self.aliases,
+            },
+        )
+    }
+}
+
+struct SavedWorldVisitor<'a> {
+    type_registry: &'a TypeRegistry,
+    aliases: &'a SaveAliases,
+}
+
+impl<'de> Visitor<'de> for SavedWorldVisitor<'_> {
+    type Value = SavedWorld;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("saved world struct")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut resources = None;
+        let mut entities = None;
+        let mut generations = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SavedWorldField::Resources => {
+                    if resources.is_some() {
+                        return Err(de::Error::duplicate_field(SAVED_WORLD_RESOURCES));
+                    }
+                    resources = Some(map.next_value_seed(SavedComponentsDeserializer {
+                        type_registry: self.type_registry,
+                        aliases: self.aliases,
+                    })?);
+                }
+                SavedWorldField::Entities => {
+                    if entities.is_some() {
+                        return Err(de::Error::duplicate_field(SAVED_WORLD_ENTITIES));
+                    }
+                    entities = Some(map.next_value_seed(SavedEntitiesDeserializer {
+                        type_registry: self.type_registry,
+                        aliases: self.aliases,
+                    })?);
+                }
+                SavedWorldField::Generations => {
+                    if generations.is_some() {
+                        return Err(de::Error::duplicate_field(SAVED_WORLD_GENERATIONS));
+                    }
+                    generations = Some(map.next_value()?);
+                }
+            }
+        }
+        let entities = entities.ok_or_else(|| de::Error::missing_field(SAVED_WORLD_ENTITIES))?;
+        Ok(SavedWorld {
+            resources: resources.unwrap_or_default(),
+            scene: DynamicScene { entities },
+            generations: generations.unwrap_or_default(),
+        })
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let resources = seq
+            .next_element_seed(SavedComponentsDeserializer {
+                type_registry: self.type_registry,
+                aliases: self.aliases,
+            })?
+            .ok_or_else(|| de::Error::missing_field(SAVED_WORLD_RESOURCES))?;
+        let entities = seq
+            .next_element_seed(SavedEntitiesDeserializer {
+                type_registry: self.type_registry,
+                aliases: self.aliases,
+            })?
+            .ok_or_else(|| de::Error::missing_field(SAVED_WORLD_ENTITIES))?;
+        let generations = seq.next_element()?.unwrap_or_default();
+        Ok(SavedWorld {
+            resources,
+            scene: DynamicScene { entities },
+            generations,
+        })
+    }
+}
+
+/// Deserializes a map of saved entities, same as [`SceneEntitiesDeserializer`](bevy::scene::serde::SceneEntitiesDeserializer),
+/// with support for [`SaveAliases`].
+struct SavedEntitiesDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+    aliases: &'a SaveAliases,
+}
+
+impl<'de> DeserializeSeed<'de> for SavedEntitiesDeserializer<'_> {
+    type Value = Vec<DynamicEntity>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SavedEntitiesDeserializer<'_> {
+    type Value = Vec<DynamicEntity>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of entities")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut entities = Vec::new();
+        while let Some(entity) = map.next_key::<u32>()? {
+            let components = map.next_value_seed(SavedEntityDeserializer {
+                type_registry: self.type_registry,
+                aliases: self.aliases,
+            })?;
+            entities.push(DynamicEntity { entity, components });
+        }
+        Ok(entities)
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(field_identifier, rename_all = "lowercase")]
+enum SavedEntityField {
+    Components,
+}
+
+/// Deserializes the components of a saved entity.
+struct SavedEntityDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+    aliases: &'a SaveAliases,
+}
+
+impl<'de> DeserializeSeed<'de> for SavedEntityDeserializer<'_> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_struct(SAVED_ENTITY_STRUCT, &[SAVED_ENTITY_COMPONENTS], self)
+    }
+}
+
+impl<'de> Visitor<'de> for SavedEntityDeserializer<'_> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("entity struct")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut components = None;
+        while let Some(key) = map.next_key()? {
+            match key {
+                SavedEntityField::Components => {
+                    if components.is_some() {
+                        return Err(de::Error::duplicate_field(SAVED_ENTITY_COMPONENTS));
+                    }
+                    components = Some(map.next_value_seed(SavedComponentsDeserializer {
+                        type_registry: self.type_registry,
+                        aliases: self.aliases,
+                    })?);
+                }
+            }
+        }
+        components.ok_or_else(|| de::Error::missing_field(SAVED_ENTITY_COMPONENTS))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        seq.next_element_seed(SavedComponentsDeserializer {
+            type_registry: self.type_registry,
+            aliases: self.aliases,
+        })?
+        .ok_or_else(|| de::Error::missing_field(SAVED_ENTITY_COMPONENTS))
+    }
+}
+
+/// Deserializes a map of reflected components or resources, keyed by their type name.
+struct SavedComponentsDeserializer<'a> {
+    type_registry: &'a TypeRegistry,
+    aliases: &'a SaveAliases,
+}
+
+impl<'de> DeserializeSeed<'de> for SavedComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for SavedComponentsDeserializer<'_> {
+    type Value = Vec<Box<dyn Reflect>>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("map of components")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let mut added = HashSet::new();
+        let mut components = Vec::new();
+        while let Some(key) = map.next_key::<&str>()? {
+            let type_name = self.aliases.resolve(key);
+            if !added.insert(type_name) {
+                return Err(de::Error::custom(format!(
+                    "duplicate component: `{type_name}`"
+                )));
+            }
+            let registration = self.type_registry.get_with_name(type_name).ok_or_else(|| {
+                de::Error::custom(format!("no registration found for `{type_name}`"))
+            })?;
+            components.push(map.next_value_seed(TypedReflectDeserializer::new(
+                registration,
+                self.type_registry,
+            ))?);
+        }
+        Ok(components)
+    }
+}
@@ -0,0 +1,272 @@
+use std::ffi::OsStr;
+
+use super::*;
+
+/// A [`Resource`] which manages named save slots within a base directory.
+///
+/// # Usage
+///
+/// Each slot is a save file named `{slot}.{extension}` within the base directory of this resource.
+/// Slot names must be valid file names (see [`SaveSlots::is_valid_name`]). Slots are saved and loaded using
+/// [`SaveLoadSlot`], and they may be listed, copied, renamed or deleted using this resource.
+///
+/// # Example
+/// ```
+/// # use bevy::prelude::*;
+/// # use bevy_atomic_save::{SaveLoadSlot, SavePlugin, SaveSlots};
+/// App::new()
+///     .add_plugin(SavePlugin::default())
+///     .insert_resource(SaveSlots::new("saves"));
+///
+/// fn quick_save(mut commands: Commands) {
+///     commands.save_slot("quick");
+/// }
+/// ```
+#[derive(Resource, Clone, Debug)]
+pub struct SaveSlots {
+    base_dir: PathBuf,
+    extension: String,
+}
+
+impl SaveSlots {
+    /// Default file extension of all save slots.
+    pub const DEFAULT_EXTENSION: &'static str = "sav";
+
+    /// Creates a new [`SaveSlots`] with the given base directory.
+    ///
+    /// The directory is created when the first slot is saved, if it does not exist.
+    pub fn new(base_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_dir: base_dir.into(),
+            extension: Self::DEFAULT_EXTENSION.to_string(),
+        }
+    }
+
+    /// Sets the file extension of all save slots.
+    pub fn with_extension(mut self, extension: impl Into<String>) -> Self {
+        self.extension = extension.into();
+        self
+    }
+
+    /// Returns the base directory of all save slots.
+    pub fn base_dir(&self) -> &Path {
+        &self.base_dir
+    }
+
+    /// Returns `true` if the given `slot` name is a single, valid file name.
+    ///
+    /// Slot names must not be empty, contain path separators, or refer to the base directory or its parent.
+    /// This ensures a slot never refers to a file outside of the base directory.
+    pub fn is_valid_name(slot: &str) -> bool {
+        let mut components = Path::new(slot).components();
+        !slot.contains(['/', '\\'])
+            && matches!(components.next(), Some(std::path::Component::Normal(name)) if name == slot)
+            && components.next().is_none()
+    }
+
+    /// Returns the path of the save file of the given `slot`.
+    ///
+    /// Returns an error of kind [`io::ErrorKind::InvalidInput`] if the slot name is not valid.
+    /// See [`SaveSlots::is_valid_name`] for details.
+    pub fn path(&self, slot: &str) -> io::Result<PathBuf> {
+        if !Self::is_valid_name(slot) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("invalid save slot name: {slot:?}"),
+            ));
+        }
+        Ok(self.base_dir.join(format!("{slot}.{}", self.extension)))
+    }
+
+    /// Returns `true` if the given `slot` is saved.
+    pub fn exists(&self, slot: &str) -> bool {
+        matches!(self.path(slot), Ok(path) if path.is_file())
+    }
+
+    /// Returns all saved slots, sorted by name.
+    ///
+    /// The [`SaveMetadata`] of each slot is read without loading it. See [`read_save_metadata`] for details.
+    pub fn list(&self) -> io::Result<Vec<SaveSlot>> {
+        let entries = match fs::read_dir(&self.base_dir) {
+            Ok(entries) => entries,
+            Err(why) if why.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(why) => return Err(why),
+        };
+        let mut slots = Vec::new();
+        for entry in entries {
+            let path = entry?.path();
+            if !path.is_file() || path.extension() != Some(OsStr::new(&self.extension)) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(OsStr::to_str) else {
+                continue;
+            };
+            slots.push(SaveSlot {
+                name: name.to_string(),
+                metadata: read_save_metadata(&path),
+                path,
+            });
+        }
+        slots.sort_by(|a, b| a.name.cmp(&b.name));
+        Ok(slots)
+    }
+
+    /// Copies the save file of slot `from` into slot `to`, replacing it if it exists.
+    ///
+    /// The copy is written atomically. See [`write_atomic`] for details.
+    pub fn copy(&self, from: &str, to: &str) -> io::Result<()> {
+        let mut source = File::open(self.path(from)?)?;
+        write_atomic(self.path(to)?, |file| {
+            io::copy(&mut source, file).map(|_| ())
+        })
+    }
+
+    /// Renames slot `from` to `to`, replacing slot `to` if it exists.
+    ///
+    /// Any backups of slot `from` (see [`BackupPolicy`]) are renamed with it, and any backups of slot `to`
+    /// are removed. Renaming a slot to itself has no effect.
+    pub fn rename(&self, from: &str, to: &str) -> io::Result<()> {
+        let (from, to) = (self.path(from)?, self.path(to)?);
+        if from == to {
+            return Ok(());
+        }
+        fs::rename(&from, &to)?;
+        rename_backups(&from, &to)
+    }
+
+    /// Deletes the save file of the given `slot`, along with any of its backups (see [`BackupPolicy`]).
+    pub fn delete(&self, slot: &str) -> io::Result<()> {
+        let path = self.path(slot)?;
+        fs::remove_file(&path)?;
+        remove_backups(&path)
+    }
+
+    fn create_base_dir(&self) {
+        if let Err(why) = fs::create_dir_all(&self.base_dir) {
+            warn!("save slot directory creation failed: {why:?}");
+        }
+    }
+}
+
+/// A saved slot, as returned by [`SaveSlots::list`].
+#[derive(Debug)]
+pub struct SaveSlot {
+    /// Name of the slot.
+    pub name: String,
+    /// Path of the save file of the slot.
+    pub path: PathBuf,
+    /// Metadata of the slot, or the cause of failure if it could not be read.
+    pub metadata: Result<SaveMetadata, LoadError>,
+}
+
+/// Trait used to save and load a [`World`] using [`SaveSlots`].
+///
+/// These functions are the same as [`SaveWorld::save`] and [`LoadWorld::load`], except that the path of
+/// the save file is resolved from the [`SaveSlots`] resource, which must exist in the [`World`].
+pub trait SaveLoadSlot {
+    /// Queues a new [`Request::Save`] into the given `slot`. See [`SaveWorld::save`] for details.
+    ///
+    /// If the slot name is not valid (see [`SaveSlots::is_valid_name`]), nothing is saved and a
+    /// [`SaveFailedEvent`] is sent instead.
+    fn save_slot(self, slot: impl Into<String>) -> RequestId;
+
+    /// Queues a new [`Request::Load`] from the given `slot`. See [`LoadWorld::load`] for details.
+    ///
+    /// If the slot name is not valid (see [`SaveSlots::is_valid_name`]), nothing is loaded and a
+    /// [`LoadFailedEvent`] is sent instead.
+    fn load_slot(self, slot: impl Into<String>) -> RequestId;
+}
+
+impl SaveLoadSlot for &mut World {
+    fn save_slot(self, slot: impl Into<String>) -> RequestId {
+        let id = RequestId::next();
+        save_slot_with_id(self, id, &slot.into());
+        id
+    }
+
+    fn load_slot(self, slot: impl Into<String>) -> RequestId {
+        let id = RequestId::next();
+        load_slot_with_id(self, id, &slot.into());
+        id
+    }
+}
+
+impl SaveLoadSlot for &mut Commands<'_, '_> {
+    fn save_slot(self, slot: impl Into<String>) -> RequestId {
+        let slot = slot.into();
+        queue_deferred(self, move |world, id| save_slot_with_id(world, id, &slot))
+    }
+
+    fn load_slot(self, slot: impl Into<String>) -> RequestId {
+        let slot = slot.into();
+        queue_deferred(self, move |world, id| load_slot_with_id(world, id, &slot))
+    }
+}
+
+/// Queues a [`Request::Save`] into the given `slot` with a previously allocated [`RequestId`].
+///
+/// If the slot name is not valid, a [`SaveFailedEvent`] is sent instead.
+fn save_slot_with_id(world: &mut World, id: RequestId, slot: &str) {
+    let slots = world.resource::<SaveSlots>();
+    match slots.path(slot) {
+        Ok(path) => {
+            slots.create_base_dir();
+            QueueWithId(world, id).save(path);
+        }
+        Err(why) => {
+            error!("save failed: {why}");
+            let path = slots.base_dir.join(slot);
+            world.send_event(SaveFailedEvent {
+                id,
+                path,
+                mode: SaveMode::Filtered,
+                error: SaveError::Io(why),
+            });
+        }
+    }
+}
+
+/// Queues a [`Request::Load`] from the given `slot` with a previously allocated [`RequestId`].
+///
+/// If the slot name is not valid, a [`LoadFailedEvent`] is sent instead.
+fn load_slot_with_id(world: &mut World, id: RequestId, slot: &str) {
+    let slots = world.resource::<SaveSlots>();
+    match slots.path(slot) {
+        Ok(path) => {
+            QueueWithId(world, id).load(path);
+        }
+        Err(why) => {
+            error!("load failed: {why}");
+            let path = slots.base_dir.join(slot);
+            world.send_event(LoadFailedEvent {
+                id,
+                path,
+                mode: LoadMode::Replace,
+                error: LoadError::Io(why),
+            });
+        }
+    }
+}
+
+/// Allocates a [`RequestId`] and queues a request with it once the given `commands` are applied.
+fn queue_deferred(
+    commands: &mut Commands,
+    queue: impl FnOnce(&mut World, RequestId) + Send + Sync + 'static,
+) -> RequestId {
+    let id = RequestId::next();
+    commands.add(move |world: &mut World| queue(world, id));
+    id
+}
+
+/// Queues a [`Request`] with a previously allocated [`RequestId`].
+struct QueueWithId<'w>(&'w mut World, RequestId);
+
+impl QueueRequest for QueueWithId<'_> {
+    fn queue_request(self, request: Request) -> RequestId {
+        let Self(world, id) = self;
+        world
+            .get_resource_or_insert_with(RequestQueue::default)
+            .push_with_id(id, request);
+        id
+    }
+}
@@ -0,0 +1,144 @@
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, FromLoaded)]
+#[reflect(Component, FromLoaded)]
+struct Target(Option<Entity>);
+
+#[derive(Component, Clone)]
+struct Chunk(u32);
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct QuestTarget(SaveRef);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Target>()
+        .register_type::<QuestTarget>()
+        .register_type::<Option<Entity>>();
+    app
+}
+
+/// Saves an entity which references another saved entity.
+fn save(path: &PathBuf) {
+    let mut app = app();
+    let target = app.world.spawn(Save).id();
+    app.world.spawn((Save, Target(Some(target))));
+    app.world.save(path);
+    app.update();
+}
+
+#[test]
+fn load_additive() {
+    let dir = test_dir("load_additive");
+    let path = dir.join("chunk.ron");
+    save(&path);
+
+    let mut app = app();
+    let existing = app.world.spawn(Save).id();
+    let existing_target = app.world.spawn((Save, Target(Some(existing)))).id();
+    app.world.load_additive(&path);
+    app.update();
+
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+    let mut query = app.world.query_filtered::<Entity, With<Save>>();
+    assert_eq!(query.iter(&app.world).count(), 4);
+    // Existing references must be left untouched:
+    assert_eq!(
+        app.world.get::<Target>(existing_target).unwrap().0,
+        Some(existing)
+    );
+    let targets: Vec<(Entity, Entity)> = app
+        .world
+        .query::<(Entity, &Target)>()
+        .iter(&app.world)
+        .filter(|(entity, _)| *entity != existing_target)
+        .map(|(entity, target)| (entity, target.0.unwrap()))
+        .collect();
+    assert_eq!(targets.len(), 1);
+    let (entity, target) = targets[0];
+    assert_ne!(target, existing);
+    assert_ne!(target, entity);
+    assert!(app.world.get_entity(target).is_some());
+}
+
+#[test]
+fn load_additive_with_marker() {
+    let dir = test_dir("load_additive_with_marker");
+    let path = dir.join("chunk.ron");
+    save(&path);
+
+    let mut app = app();
+    app.world.spawn(Save);
+    app.world.load_additive_with_marker(&path, Chunk(7));
+    app.update();
+
+    let chunks: Vec<u32> = app
+        .world
+        .query::<&Chunk>()
+        .iter(&app.world)
+        .map(|chunk| chunk.0)
+        .collect();
+    assert_eq!(chunks, [7, 7]);
+}
+
+#[test]
+fn load_additive_twice() {
+    let dir = test_dir("load_additive_twice");
+    let path = dir.join("chunk.ron");
+    save(&path);
+
+    let mut app = app();
+    app.world.load_additive(&path);
+    app.update();
+    app.world.load_additive(&path);
+    app.update();
+
+    let mut query = app.world.query::<&SaveId>();
+    let mut ids: Vec<u64> = query.iter(&app.world).map(|id| id.get()).collect();
+    ids.sort();
+    ids.dedup();
+    assert_eq!(ids.len(), 4);
+    assert_eq!(app.world.resource::<SaveIdIndex>().len(), 4);
+}
+
+#[test]
+fn load_additive_remaps_save_refs() {
+    let dir = test_dir("load_additive_remaps_save_refs");
+    let path = dir.join("chunk.ron");
+
+    {
+        let mut app = app();
+        let target = app.world.spawn(Save).id();
+        app.update();
+        let target_ref = app
+            .world
+            .resource::<SaveIdIndex>()
+            .save_ref(target)
+            .unwrap();
+        app.world.spawn((Save, QuestTarget(target_ref)));
+        app.update();
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    // Existing entities use the same IDs as the saved entities:
+    let existing: Vec<Entity> = (0..2).map(|_| app.world.spawn(Save).id()).collect();
+    app.update();
+    app.world.load_additive(&path);
+    app.update();
+
+    let &QuestTarget(target_ref) = app.world.query::<&QuestTarget>().single(&app.world);
+    let index = app.world.resource::<SaveIdIndex>();
+    assert_eq!(index.len(), 4);
+    let target = target_ref.entity(index).unwrap();
+    assert!(!existing.contains(&target));
+    assert!(app.world.get::<QuestTarget>(target).is_none());
+}
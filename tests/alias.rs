@@ -0,0 +1,63 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, PartialEq, Debug)]
+#[reflect(Component)]
+struct Pawn(u32);
+
+#[derive(Resource, Default, Reflect, PartialEq, Debug)]
+#[reflect(Resource)]
+struct Clock(f32);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Pawn>()
+        .register_saved_resource::<Clock>();
+    app
+}
+
+const LEGACY_SAVE: &str = r#"(
+    resources: { "game::old::Clock": (2.5) },
+    entities: { 0: (components: { "game::old::Pawn": (7) }) },
+)"#;
+
+#[test]
+fn load_with_alias() {
+    let dir = test_dir("load_with_alias");
+    let path = dir.join("world.ron");
+    fs::write(&path, LEGACY_SAVE).unwrap();
+
+    let mut app = app();
+    app.register_save_alias("game::old::Pawn", std::any::type_name::<Pawn>())
+        .register_save_alias("game::old::Clock", std::any::type_name::<Clock>());
+    app.world.load(&path);
+    app.update();
+
+    let mut query = app.world.query::<&Pawn>();
+    assert_eq!(query.single(&app.world), &Pawn(7));
+    assert_eq!(app.world.resource::<Clock>(), &Clock(2.5));
+}
+
+#[test]
+fn load_without_alias() {
+    let dir = test_dir("load_without_alias");
+    let path = dir.join("world.ron");
+    fs::write(&path, LEGACY_SAVE).unwrap();
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(matches!(event.error, LoadError::Deserialize(_)));
+}
@@ -0,0 +1,132 @@
+use std::path::PathBuf;
+use std::time::Duration;
+
+use bevy::ecs::schedule::ShouldRun;
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Health(u32);
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, SaveSkip)]
+struct Cache(u32);
+
+#[derive(Resource, Default)]
+struct Paused(bool);
+
+fn not_paused(paused: Res<Paused>) -> ShouldRun {
+    if paused.0 {
+        ShouldRun::No
+    } else {
+        ShouldRun::Yes
+    }
+}
+
+fn app(paths: [PathBuf; 2]) -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Health>()
+        .register_type::<Cache>()
+        .init_resource::<Paused>()
+        // Zero interval autosaves every frame:
+        .add_plugin(AutosavePlugin::new(Duration::ZERO, paths).with_run_criteria(not_paused));
+    app
+}
+
+/// Returns the paths written during the last update, without consuming the events.
+fn saved_paths(app: &mut App) -> Vec<PathBuf> {
+    app.world
+        .resource::<Events<SavedEvent>>()
+        .iter_current_update_events()
+        .filter(|event| !event.skipped)
+        .map(|event| event.path.clone())
+        .collect()
+}
+
+#[test]
+fn autosave_rolls_over_paths() {
+    let dir = test_dir("autosave_rolls_over_paths");
+    let paths = [dir.join("autosave_1.sav"), dir.join("autosave_2.sav")];
+
+    let mut app = app(paths.clone());
+    let entity = app.world.spawn((Save, Health(10))).id();
+    for (health, path) in [(9, 0), (8, 1), (7, 0)] {
+        app.update();
+        assert_eq!(saved_paths(&mut app), [paths[path].clone()]);
+        app.world.get_mut::<Health>(entity).unwrap().0 = health;
+    }
+}
+
+#[test]
+fn autosave_skips_unchanged() {
+    let dir = test_dir("autosave_skips_unchanged");
+    let paths = [dir.join("autosave_1.sav"), dir.join("autosave_2.sav")];
+
+    let mut app = app(paths.clone());
+    let entity = app.world.spawn((Save, Health(10))).id();
+    app.update();
+    assert_eq!(saved_paths(&mut app), [paths[0].clone()]);
+
+    app.update();
+    assert!(saved_paths(&mut app).is_empty());
+    assert!(!paths[1].exists());
+
+    // The skipped path is used by the next autosave:
+    app.world.get_mut::<Health>(entity).unwrap().0 = 9;
+    app.update();
+    assert_eq!(saved_paths(&mut app), [paths[1].clone()]);
+}
+
+#[test]
+fn autosave_run_criteria() {
+    let dir = test_dir("autosave_run_criteria");
+    let paths = [dir.join("autosave_1.sav"), dir.join("autosave_2.sav")];
+
+    let mut app = app(paths.clone());
+    app.world.spawn((Save, Health(10)));
+    app.world.resource_mut::<Paused>().0 = true;
+    app.update();
+    assert!(saved_paths(&mut app).is_empty());
+
+    app.world.resource_mut::<Paused>().0 = false;
+    app.update();
+    assert_eq!(saved_paths(&mut app), [paths[0].clone()]);
+}
+
+#[test]
+fn autosave_skips_unsaved_changes() {
+    let dir = test_dir("autosave_skips_unsaved_changes");
+    let paths = [dir.join("autosave_1.sav"), dir.join("autosave_2.sav")];
+
+    let mut app = app(paths.clone());
+    let entity = app.world.spawn((Save, Health(10), Cache(0))).id();
+    app.update();
+    assert_eq!(saved_paths(&mut app), [paths[0].clone()]);
+
+    app.world.get_mut::<Cache>(entity).unwrap().0 = 1;
+    app.update();
+    assert!(saved_paths(&mut app).is_empty());
+}
+
+#[test]
+fn autosave_retries_failed() {
+    let dir = test_dir("autosave_retries_failed");
+    // The first path can not be written, since its directory does not exist:
+    let paths = [
+        dir.join("missing/autosave_1.sav"),
+        dir.join("autosave_2.sav"),
+    ];
+
+    let mut app = app(paths.clone());
+    app.world.spawn((Save, Health(10)));
+    app.update();
+    assert!(saved_paths(&mut app).is_empty());
+    assert!(!app.world.resource::<Events<SaveFailedEvent>>().is_empty());
+
+    app.update();
+    assert_eq!(saved_paths(&mut app), [paths[1].clone()]);
+}
@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Generation(u32);
+
+fn app(backups: BackupPolicy) -> App {
+    let mut app = test_app(SavePlugin::default().with_backups(backups));
+    app.register_type::<Generation>();
+    app
+}
+
+fn save_generations(app: &mut App, path: &PathBuf, count: u32) {
+    for generation in 0..count {
+        let entity = app.world.spawn((Save, Generation(generation))).id();
+        app.world.save(path);
+        app.update();
+        app.world.despawn(entity);
+    }
+}
+
+fn loaded_generation(app: &mut App) -> u32 {
+    let mut query = app.world.query::<&Generation>();
+    query.single(&app.world).0
+}
+
+#[test]
+fn numbered_backups() {
+    let dir = test_dir("numbered_backups");
+    let path = dir.join("world.ron");
+
+    let mut app = app(BackupPolicy::Numbered(2));
+    save_generations(&mut app, &path, 4);
+
+    let backups = BackupPolicy::Numbered(2).backups(&path);
+    assert_eq!(backups, [dir.join("world.ron.1"), dir.join("world.ron.2")]);
+    assert!(!dir.join("world.ron.3").exists());
+
+    app.world.load(&backups[1]);
+    app.update();
+    assert_eq!(loaded_generation(&mut app), 1);
+}
+
+#[test]
+fn timestamped_backups() {
+    let dir = test_dir("timestamped_backups");
+    let path = dir.join("world.ron");
+
+    let mut app = app(BackupPolicy::Timestamped(2));
+    save_generations(&mut app, &path, 4);
+
+    // Backups made within the same millisecond must not replace each other:
+    let backups = BackupPolicy::Timestamped(2).backups(&path);
+    assert_eq!(backups.len(), 2);
+
+    app.world.load(&backups[0]);
+    app.update();
+    assert_eq!(loaded_generation(&mut app), 2);
+}
+
+#[test]
+fn failed_save_keeps_backups() {
+    let dir = test_dir("failed_save_keeps_backups");
+    let path = dir.join("world.ron");
+
+    let mut app = app(BackupPolicy::Numbered(2));
+    save_generations(&mut app, &path, 3);
+
+    // A directory at the temporary file path makes the next save fail:
+    fs::create_dir(dir.join("world.ron.tmp")).unwrap();
+    app.world.save(&path);
+    app.update();
+    assert!(app
+        .world
+        .resource_mut::<Events<SaveFailedEvent>>()
+        .drain()
+        .next()
+        .is_some());
+
+    let backups = BackupPolicy::Numbered(2).backups(&path);
+    assert_eq!(backups, [dir.join("world.ron.1"), dir.join("world.ron.2")]);
+    for (backup, generation) in backups.iter().zip([1, 0]) {
+        app.world.load(backup);
+        app.update();
+        assert_eq!(loaded_generation(&mut app), generation);
+    }
+}
+
+#[test]
+fn load_latest_valid() {
+    let dir = test_dir("load_latest_valid");
+    let path = dir.join("world.ron");
+
+    let mut app = app(BackupPolicy::Numbered(2));
+    save_generations(&mut app, &path, 3);
+
+    // Corrupt the primary save file and its newest backup:
+    for corrupted in [path.clone(), dir.join("world.ron.1")] {
+        let mut bytes = fs::read(&corrupted).unwrap();
+        bytes.push(b' ');
+        fs::write(&corrupted, bytes).unwrap();
+    }
+
+    app.world.load_latest_valid(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(event.path, dir.join("world.ron.2"));
+    assert_eq!(loaded_generation(&mut app), 0);
+}
+
+#[test]
+fn load_latest_valid_without_backups() {
+    let dir = test_dir("load_latest_valid_without_backups");
+    let path = dir.join("world.ron");
+
+    let mut app = app(BackupPolicy::Numbered(2));
+    app.world.load_latest_valid(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(event.path, path);
+    assert!(matches!(event.error, LoadError::Io(_)));
+}
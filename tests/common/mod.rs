@@ -0,0 +1,26 @@
+//! Fixtures shared by all integration tests.
+
+#![allow(dead_code)]
+
+use std::fs;
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+/// Returns an empty directory for the test with the given `name`.
+pub fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir()
+        .join("bevy_atomic_save")
+        .join(format!("{name}-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Returns a minimal [`App`] with the given [`SavePlugin`].
+pub fn test_app(plugin: SavePlugin) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins).add_plugin(plugin);
+    app
+}
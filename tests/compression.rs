@@ -0,0 +1,141 @@
+use std::fs;
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, PartialEq, Debug, Clone)]
+#[reflect(Component)]
+struct Stats {
+    name: String,
+    health: f32,
+}
+
+fn app(compression: Compression) -> App {
+    let mut app = test_app(SavePlugin::default().with_compression(compression));
+    app.register_type::<Stats>();
+    app
+}
+
+fn save_compressed(path: &Path, compression: Option<Compression>) -> Vec<SaveFailedEvent> {
+    let mut app = app(Compression::None);
+    app.world.spawn((
+        Save,
+        Stats {
+            name: "pawn".to_string(),
+            health: 0.5,
+        },
+    ));
+    let mut request = Request::save(path);
+    if let Some(compression) = compression {
+        request = request.with_compression(compression);
+    }
+    app.world.queue_request(request);
+    app.update();
+    app.world
+        .resource_mut::<Events<SaveFailedEvent>>()
+        .drain()
+        .collect()
+}
+
+fn round_trip(name: &str, compression: Compression) {
+    let dir = test_dir(name);
+    let path = dir.join("world.sav");
+    assert!(save_compressed(&path, Some(compression)).is_empty());
+
+    let mut app = app(Compression::None);
+    app.world.load(&path);
+    app.update();
+
+    let mut query = app.world.query::<&Stats>();
+    assert_eq!(query.single(&app.world).name, "pawn");
+}
+
+#[test]
+fn no_compression() {
+    round_trip("compression_none", Compression::None);
+}
+
+#[test]
+#[cfg(feature = "zstd")]
+fn zstd() {
+    round_trip("compression_zstd", Compression::Zstd);
+}
+
+#[test]
+#[cfg(feature = "gzip")]
+fn gzip() {
+    round_trip("compression_gzip", Compression::Gzip);
+}
+
+#[test]
+#[cfg(feature = "lz4")]
+fn lz4() {
+    round_trip("compression_lz4", Compression::Lz4);
+}
+
+#[test]
+fn default_compression() {
+    let dir = test_dir("default_compression");
+    let path = dir.join("world.sav");
+
+    let mut app = app(Compression::Gzip);
+    app.world.spawn(Save);
+    app.world.save(&path);
+    app.update();
+
+    if Compression::Gzip.is_supported() {
+        let bytes = fs::read(&path).unwrap();
+        let (header, _) = SaveHeader::read(&bytes).unwrap();
+        assert_eq!(header.compression, Compression::Gzip);
+    } else {
+        let event = app
+            .world
+            .resource_mut::<Events<SaveFailedEvent>>()
+            .drain()
+            .next()
+            .unwrap();
+        assert!(matches!(
+            event.error,
+            SaveError::UnsupportedCompression(Compression::Gzip)
+        ));
+    }
+}
+
+#[test]
+fn unsupported_compression() {
+    if Compression::Lz4.is_supported() {
+        return;
+    }
+    let dir = test_dir("unsupported_compression");
+    let path = dir.join("world.sav");
+    let errors = save_compressed(&path, Some(Compression::Lz4));
+    assert!(matches!(
+        errors[0].error,
+        SaveError::UnsupportedCompression(Compression::Lz4)
+    ));
+    assert!(!path.exists());
+
+    let header = SaveHeader {
+        compression: Compression::Lz4,
+        ..default()
+    };
+    fs::write(&path, header.to_bytes(&[]).unwrap()).unwrap();
+    let mut app = app(Compression::None);
+    app.world.load(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(matches!(
+        event.error,
+        LoadError::UnsupportedCompression(Compression::Lz4)
+    ));
+}
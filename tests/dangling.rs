@@ -0,0 +1,164 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, FromLoaded)]
+#[reflect(Component)]
+struct Target(Option<Entity>);
+
+#[derive(Component, Default, Reflect, FromLoaded)]
+#[reflect(Component, FromLoaded)]
+struct Targets(Vec<Entity>);
+
+/// Implemented manually, which only requires [`FromLoaded::try_from_loaded`].
+#[derive(Component, Reflect)]
+#[reflect(Component, FromLoaded)]
+struct Leader(Entity);
+
+impl Default for Leader {
+    fn default() -> Self {
+        Self(PLACEHOLDER_ENTITY)
+    }
+}
+
+impl FromLoaded for Leader {
+    fn try_from_loaded(&mut self, loaded: &Loaded) -> Result<(), DanglingEntities> {
+        self.0.try_from_loaded(loaded)
+    }
+}
+
+#[derive(Component, Default, Reflect, FromLoaded)]
+#[reflect(Component, FromLoaded)]
+struct Escort {
+    #[from_loaded(try_with = "remap_escort")]
+    leader: Option<Entity>,
+}
+
+fn remap_escort(leader: &mut Option<Entity>, loaded: &Loaded) -> Result<(), DanglingEntities> {
+    leader.try_from_loaded(loaded)
+}
+
+fn app(policy: DanglingPolicy) -> App {
+    let mut app = test_app(SavePlugin::default().with_dangling_policy(policy));
+    app.register_type::<Target>()
+        .register_type::<Targets>()
+        .register_type::<Option<Entity>>()
+        .register_type::<Vec<Entity>>()
+        .register_type::<Leader>()
+        .register_type::<Escort>()
+        .register_loaded::<Target>();
+    app
+}
+
+/// Saves an entity with references to a saved entity, and to an entity which is not saved.
+fn save(path: &Path) {
+    let mut app = app(DanglingPolicy::Panic);
+    let saved = app.world.spawn(Save).id();
+    let unsaved = app.world.spawn_empty().id();
+    app.world
+        .spawn((Save, Target(Some(unsaved)), Targets(vec![saved, unsaved])));
+    app.world.save(path);
+    app.update();
+}
+
+fn load(path: &Path, policy: DanglingPolicy) -> App {
+    let mut app = app(policy);
+    for _ in 0..4 {
+        app.world.spawn_empty();
+    }
+    app.world.load(path);
+    app.update();
+    app
+}
+
+#[test]
+#[should_panic]
+fn dangling_panic() {
+    let dir = test_dir("dangling_panic");
+    let path = dir.join("world.ron");
+    save(&path);
+    load(&path, DanglingPolicy::Panic);
+}
+
+#[test]
+fn dangling_placeholder() {
+    let dir = test_dir("dangling_placeholder");
+    let path = dir.join("world.ron");
+    save(&path);
+    let mut app = load(&path, DanglingPolicy::Placeholder);
+
+    let (target, targets) = app.world.query::<(&Target, &Targets)>().single(&app.world);
+    assert_eq!(target.0, None);
+    assert_eq!(targets.0.len(), 2);
+    assert!(app.world.get_entity(targets.0[0]).is_some());
+    assert_eq!(targets.0[1], PLACEHOLDER_ENTITY);
+
+    let events: Vec<DanglingEntitiesEvent> = app
+        .world
+        .resource_mut::<Events<DanglingEntitiesEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(events.len(), 2);
+    assert!(events
+        .iter()
+        .all(|event| event.dangling.0.len() == 1 && event.policy == DanglingPolicy::Placeholder));
+}
+
+#[test]
+fn dangling_remove() {
+    let dir = test_dir("dangling_remove");
+    let path = dir.join("world.ron");
+    save(&path);
+    let mut app = load(&path, DanglingPolicy::Remove);
+
+    let mut query = app.world.query_filtered::<Entity, With<Save>>();
+    assert_eq!(query.iter(&app.world).count(), 2);
+    let mut query = app.world.query::<Or<(With<Target>, With<Targets>)>>();
+    assert_eq!(query.iter(&app.world).count(), 0);
+}
+
+#[test]
+fn dangling_despawn() {
+    let dir = test_dir("dangling_despawn");
+    let path = dir.join("world.ron");
+    save(&path);
+    let mut app = load(&path, DanglingPolicy::Despawn);
+
+    let mut query = app.world.query_filtered::<Entity, With<Save>>();
+    assert_eq!(query.iter(&app.world).count(), 1);
+    // The owner is despawned by whichever component is updated first:
+    assert!(!app
+        .world
+        .resource::<Events<DanglingEntitiesEvent>>()
+        .is_empty());
+}
+
+#[test]
+fn dangling_custom() {
+    let dir = test_dir("dangling_custom");
+    let path = dir.join("world.ron");
+    {
+        let mut app = app(DanglingPolicy::Panic);
+        let unsaved = app.world.spawn_empty().id();
+        app.world.spawn((
+            Save,
+            Leader(unsaved),
+            Escort {
+                leader: Some(unsaved),
+            },
+        ));
+        app.world.save(&path);
+        app.update();
+    }
+    let mut app = load(&path, DanglingPolicy::Placeholder);
+
+    let (leader, escort) = app.world.query::<(&Leader, &Escort)>().single(&app.world);
+    assert_eq!(leader.0, PLACEHOLDER_ENTITY);
+    assert_eq!(escort.leader, None);
+    let events = app.world.resource::<Events<DanglingEntitiesEvent>>();
+    assert_eq!(events.len(), 2);
+}
@@ -0,0 +1,121 @@
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, FromLoaded)]
+#[reflect(Component, FromLoaded)]
+struct Links {
+    next: Option<Entity>,
+    #[from_loaded(skip)]
+    skipped: Option<Entity>,
+    #[from_loaded(with = "clear")]
+    cleared: Option<Entity>,
+}
+
+fn clear(entity: &mut Option<Entity>, _: &Loaded) {
+    *entity = None;
+}
+
+#[derive(FromLoaded)]
+enum Order {
+    Idle,
+    Follow(Entity),
+    Attack {
+        target: Entity,
+        #[from_loaded(skip)]
+        origin: Entity,
+    },
+}
+
+#[derive(FromLoaded)]
+struct Wrapper<T>(T);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Links>()
+        .register_type::<Option<Entity>>();
+    app
+}
+
+#[test]
+fn derive_from_loaded() {
+    let dir = test_dir("derive_from_loaded");
+    let path = dir.join("world.ron");
+
+    let (a, b) = {
+        let mut app = app();
+        let a = app.world.spawn(Save).id();
+        let b = app.world.spawn(Save).id();
+        app.world.entity_mut(a).insert(Links {
+            next: Some(b),
+            skipped: Some(b),
+            cleared: Some(b),
+        });
+        app.world.save(&path);
+        app.update();
+        (a, b)
+    };
+
+    let mut app = app();
+    for _ in 0..4 {
+        app.world.spawn_empty();
+    }
+    app.world.load(&path);
+    app.update();
+
+    let (entity, links) = app.world.query::<(Entity, &Links)>().single(&app.world);
+    let next = links.next.unwrap();
+    assert_ne!(next, b);
+    assert_ne!(next, entity);
+    assert!(app.world.get_entity(next).is_some());
+    assert_eq!(links.skipped, Some(b));
+    assert_eq!(links.cleared, None);
+    assert_ne!(entity, a);
+}
+
+#[test]
+fn derive_from_loaded_enum() {
+    let dir = test_dir("derive_from_loaded_enum");
+    let path = dir.join("world.ron");
+
+    let saved = {
+        let mut app = app();
+        let entity = app.world.spawn(Save).id();
+        app.world.save(&path);
+        app.update();
+        entity
+    };
+
+    let mut app = app();
+    for _ in 0..4 {
+        app.world.spawn_empty();
+    }
+    app.world.load(&path);
+    app.add_system_to_stage(SaveStage::PostLoad, move |loaded: Res<Loaded>| {
+        let entity = loaded.entity(saved).unwrap();
+
+        let mut order = Order::Follow(saved);
+        order.from_loaded(&loaded);
+        assert!(matches!(order, Order::Follow(e) if e == entity));
+
+        let mut order = Order::Attack {
+            target: saved,
+            origin: saved,
+        };
+        order.from_loaded(&loaded);
+        assert!(
+            matches!(order, Order::Attack { target, origin } if target == entity && origin == saved)
+        );
+
+        let mut order = Order::Idle;
+        order.from_loaded(&loaded);
+        assert!(matches!(order, Order::Idle));
+
+        let mut wrapper = Wrapper(Some(saved));
+        wrapper.from_loaded(&loaded);
+        assert_eq!(wrapper.0, Some(entity));
+    });
+    app.update();
+}
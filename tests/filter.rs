@@ -0,0 +1,140 @@
+use std::path::Path;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+struct Profile;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Health(u32);
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Score(u32);
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component, SaveSkip)]
+struct PathCache(Vec<u32>);
+
+fn app(plugin: SavePlugin) -> App {
+    let mut app = test_app(plugin);
+    app.register_type::<Health>()
+        .register_type::<Score>()
+        .register_type::<PathCache>()
+        .register_type::<Vec<u32>>();
+    app
+}
+
+/// Returns which of the saved components exist on the loaded entity.
+fn load(path: &Path, load: impl FnOnce(&mut World, &Path)) -> (bool, bool, bool) {
+    let mut app = app(SavePlugin::default());
+    load(&mut app.world, path);
+    app.update();
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+    let mut query = app
+        .world
+        .query::<(Option<&Health>, Option<&Score>, Option<&PathCache>)>();
+    let (health, score, path_cache) = query.single(&app.world);
+    (health.is_some(), score.is_some(), path_cache.is_some())
+}
+
+#[test]
+fn save_skip() {
+    let dir = test_dir("save_skip");
+    let path = dir.join("world.ron");
+
+    let mut app = app(SavePlugin::default());
+    app.world
+        .spawn((Save, Health(1), Score(2), PathCache(vec![3])));
+    app.world.save(&path);
+    app.update();
+
+    assert_eq!(
+        load(&path, |world, path| {
+            world.load(path);
+        }),
+        (true, true, false)
+    );
+}
+
+#[test]
+fn save_with_global_filter() {
+    let dir = test_dir("save_with_global_filter");
+    let path = dir.join("world.ron");
+
+    let mut app = app(SavePlugin::default().with_filter(SaveFilter::default().deny::<Score>()));
+    app.world
+        .spawn((Save, Health(1), Score(2), PathCache(vec![3])));
+    app.world.save(&path);
+    app.update();
+
+    assert_eq!(
+        load(&path, |world, path| {
+            world.load(path);
+        }),
+        (true, false, false)
+    );
+}
+
+#[test]
+fn save_with_request_filter() {
+    let dir = test_dir("save_with_request_filter");
+    let path = dir.join("world.ron");
+
+    let mut app = app(SavePlugin::default());
+    app.world
+        .spawn((Save, Health(1), Score(2), PathCache(vec![3])));
+    app.world.save_with_filter(
+        &path,
+        SaveFilter::default().allow::<Score>().allow::<PathCache>(),
+    );
+    app.update();
+
+    assert_eq!(
+        load(&path, |world, path| {
+            world.load(path);
+        }),
+        (false, true, false)
+    );
+}
+
+#[test]
+fn save_with_group_filter() {
+    let dir = test_dir("save_with_group_filter");
+    let path = dir.join("profile.ron");
+
+    let mut app = app(SavePlugin::default()
+        .with_filter(SaveFilter::default().deny::<Health>())
+        .with_group_filter::<Profile>(SaveFilter::default().deny::<Score>()));
+    app.world.spawn((
+        SaveGroup::<Profile>::default(),
+        Health(1),
+        Score(2),
+        PathCache(vec![3]),
+    ));
+    app.world.save_group::<Profile>(&path);
+    app.update();
+
+    assert_eq!(
+        load(&path, |world, path| {
+            world.load_group::<Profile>(path);
+        }),
+        (false, false, false)
+    );
+}
+
+#[test]
+fn filter_and() {
+    let filter = SaveFilter::default()
+        .allow::<Health>()
+        .allow::<Score>()
+        .and(&SaveFilter::default().allow::<Score>().deny::<PathCache>());
+    assert_eq!(
+        filter,
+        SaveFilter::default().allow::<Score>().deny::<PathCache>()
+    );
+}
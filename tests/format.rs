@@ -0,0 +1,115 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, FromReflect, PartialEq, Debug, Clone)]
+#[reflect(Component)]
+struct Stats {
+    name: String,
+    health: f32,
+    level: Option<u32>,
+    state: State,
+    items: Vec<u32>,
+}
+
+#[derive(Default, Reflect, FromReflect, PartialEq, Debug, Clone)]
+enum State {
+    #[default]
+    Idle,
+    Moving {
+        speed: f32,
+    },
+}
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Stats>()
+        .register_type::<State>()
+        .register_type::<Option<u32>>()
+        .register_type::<Vec<u32>>();
+    app
+}
+
+fn round_trip(name: &str, format: SaveFormat) {
+    let dir = test_dir(name);
+    let path = dir.join("world.sav");
+    let stats = Stats {
+        name: "pawn".to_string(),
+        health: 0.5,
+        level: Some(3),
+        state: State::Moving { speed: 2.0 },
+        items: vec![1, 2, 3],
+    };
+
+    {
+        let mut app = app();
+        app.world.spawn((Save, stats.clone()));
+        app.world.save_with_format(&path, format);
+        app.update();
+        assert!(app.world.resource::<Events<SaveFailedEvent>>().is_empty());
+    }
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+
+    let mut query = app.world.query::<&Stats>();
+    assert_eq!(query.single(&app.world), &stats);
+}
+
+#[test]
+fn ron() {
+    round_trip("format_ron", SaveFormat::Ron);
+}
+
+#[test]
+#[cfg(feature = "bincode")]
+fn bincode() {
+    round_trip("format_bincode", SaveFormat::Bincode);
+}
+
+#[test]
+#[cfg(feature = "postcard")]
+fn postcard() {
+    round_trip("format_postcard", SaveFormat::Postcard);
+}
+
+#[test]
+#[cfg(feature = "rmp-serde")]
+fn message_pack() {
+    round_trip("format_message_pack", SaveFormat::MessagePack);
+}
+
+#[test]
+fn unsupported_format() {
+    let dir = test_dir("unsupported_format");
+    let path = dir.join("world.sav");
+    let header = SaveHeader {
+        format: SaveFormat::Postcard,
+        ..default()
+    };
+    fs::write(&path, header.to_bytes(&[]).unwrap()).unwrap();
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    if SaveFormat::Postcard.is_supported() {
+        assert!(matches!(event.error, LoadError::Deserialize(_)));
+    } else {
+        assert!(matches!(
+            event.error,
+            LoadError::UnsupportedFormat(SaveFormat::Postcard)
+        ));
+    }
+}
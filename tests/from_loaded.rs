@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+use smallvec::{smallvec, SmallVec};
+
+mod common;
+use common::*;
+
+/// Saves two entities, loads them into a new app with shifted indices, and calls `f` during [`SaveStage::PostLoad`].
+fn with_loaded(name: &str, f: impl Fn(&Loaded, [Entity; 2], [Entity; 2]) + Send + Sync + 'static) {
+    let dir = test_dir(name);
+    let path = dir.join("world.ron");
+
+    let saved = {
+        let mut app = test_app(SavePlugin::default());
+        let saved = [app.world.spawn(Save).id(), app.world.spawn(Save).id()];
+        app.world.save(&path);
+        app.update();
+        saved
+    };
+
+    let mut app = test_app(SavePlugin::default());
+    for _ in 0..4 {
+        app.world.spawn_empty();
+    }
+    app.world.load(&path);
+    app.add_system_to_stage(SaveStage::PostLoad, move |loaded: Res<Loaded>| {
+        let entities = saved.map(|entity| loaded.entity(entity).unwrap());
+        assert_ne!(entities, saved);
+        f(&loaded, saved, entities);
+    });
+    app.update();
+}
+
+#[test]
+fn from_loaded_sequences() {
+    with_loaded("from_loaded_sequences", |loaded, [a, b], [x, y]| {
+        let mut vec = vec![a, b];
+        vec.from_loaded(loaded);
+        assert_eq!(vec, [x, y]);
+
+        let mut deque = VecDeque::from([a, b]);
+        deque.from_loaded(loaded);
+        assert_eq!(deque, [x, y]);
+
+        let mut array = [a, b];
+        array.from_loaded(loaded);
+        assert_eq!(array, [x, y]);
+
+        let mut small: SmallVec<[Entity; 2]> = smallvec![a, b];
+        small.from_loaded(loaded);
+        assert_eq!(small.as_slice(), [x, y]);
+
+        let mut boxed = Box::new(vec![Some(a), None]);
+        boxed.from_loaded(loaded);
+        assert_eq!(*boxed, [Some(x), None]);
+    });
+}
+
+#[test]
+fn from_loaded_maps_and_sets() {
+    with_loaded("from_loaded_maps_and_sets", |loaded, [a, b], [x, y]| {
+        let mut map = HashMap::from([(1, a), (2, b)]);
+        map.from_loaded(loaded);
+        assert_eq!(map, HashMap::from([(1, x), (2, y)]));
+
+        let mut map = bevy::utils::HashMap::default();
+        map.insert("a", a);
+        map.from_loaded(loaded);
+        assert_eq!(map["a"], x);
+
+        let mut set = HashSet::from([a, b]);
+        set.from_loaded(loaded);
+        assert_eq!(set, HashSet::from([x, y]));
+        assert!(set.contains(&x));
+
+        let mut set = bevy::utils::HashSet::default();
+        set.insert(b);
+        set.from_loaded(loaded);
+        assert!(set.contains(&y));
+    });
+}
+
+#[test]
+fn from_loaded_tuples() {
+    with_loaded("from_loaded_tuples", |loaded, [a, b], [x, y]| {
+        let mut pair = (a, Some(b));
+        pair.from_loaded(loaded);
+        assert_eq!(pair, (x, Some(y)));
+
+        let mut nested = ((a,), vec![(b, a)]);
+        nested.from_loaded(loaded);
+        assert_eq!(nested, ((x,), vec![(y, x)]));
+    });
+}
@@ -0,0 +1,141 @@
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+struct Profile;
+
+struct Settings;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Name(String);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Name>();
+    app
+}
+
+fn names<F: bevy::ecs::query::ReadOnlyWorldQuery>(app: &mut App) -> Vec<String> {
+    let mut names: Vec<String> = app
+        .world
+        .query_filtered::<&Name, F>()
+        .iter(&app.world)
+        .map(|name| name.0.clone())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn save_groups() {
+    let dir = test_dir("save_groups");
+    let profile_path = dir.join("profile.ron");
+    let settings_path = dir.join("settings.ron");
+
+    {
+        let mut app = app();
+        app.world
+            .spawn((SaveGroup::<Profile>::default(), Name("player".into())));
+        app.world
+            .spawn((SaveGroup::<Settings>::default(), Name("audio".into())));
+        app.world.spawn((Save, Name("world".into())));
+        app.world.save_group::<Profile>(&profile_path);
+        app.update();
+        app.world.save_group::<Settings>(&settings_path);
+        app.update();
+    }
+
+    let mut app = app();
+    app.world.load_group::<Profile>(&profile_path);
+    app.update();
+    assert_eq!(names::<()>(&mut app), ["player"]);
+
+    app.world.load_group::<Settings>(&settings_path);
+    app.update();
+    assert_eq!(names::<()>(&mut app), ["audio", "player"]);
+    assert_eq!(names::<With<SaveGroup<Profile>>>(&mut app), ["player"]);
+    assert_eq!(names::<With<SaveGroup<Settings>>>(&mut app), ["audio"]);
+    assert!(names::<With<Save>>(&mut app).is_empty());
+}
+
+#[test]
+fn load_group_unloads_only_group() {
+    let dir = test_dir("load_group_unloads_only_group");
+    let path = dir.join("profile.ron");
+
+    {
+        let mut app = app();
+        app.world
+            .spawn((SaveGroup::<Profile>::default(), Name("saved".into())));
+        app.world.save_group::<Profile>(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    app.world
+        .spawn((SaveGroup::<Profile>::default(), Name("current".into())));
+    app.world
+        .spawn((UnloadGroup::<Profile>::default(), Name("unloaded".into())));
+    app.world
+        .spawn((SaveGroup::<Settings>::default(), Name("settings".into())));
+    app.world.spawn((Save, Name("world".into())));
+    app.world.load_group::<Profile>(&path);
+    app.update();
+
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+    assert_eq!(names::<()>(&mut app), ["saved", "settings", "world"]);
+    assert_eq!(names::<With<Save>>(&mut app), ["world"]);
+}
+
+#[test]
+fn load_world_keeps_groups() {
+    let dir = test_dir("load_world_keeps_groups");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.world.spawn((Save, Name("world".into())));
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    app.world
+        .spawn((SaveGroup::<Profile>::default(), Name("player".into())));
+    app.world.spawn((Save, Name("current".into())));
+    app.world.load(&path);
+    app.update();
+
+    assert_eq!(names::<()>(&mut app), ["player", "world"]);
+}
+
+#[test]
+fn save_group_ids() {
+    let dir = test_dir("save_group_ids");
+    let path = dir.join("profile.ron");
+
+    let (id, index_len) = {
+        let mut app = app();
+        let entity = app.world.spawn(SaveGroup::<Profile>::default()).id();
+        app.world.save_group::<Profile>(&path);
+        app.update();
+        let id = *app.world.get::<SaveId>(entity).unwrap();
+
+        // Once a group is saved, new entities in it are assigned an ID:
+        let entity = app.world.spawn(SaveGroup::<Profile>::default()).id();
+        app.update();
+        assert!(app.world.get::<SaveId>(entity).is_some());
+        (id, app.world.resource::<SaveIdIndex>().len())
+    };
+    assert_eq!(index_len, 2);
+
+    let mut app = app();
+    app.world.load_group::<Profile>(&path);
+    app.update();
+    let index = app.world.resource::<SaveIdIndex>();
+    let entity = index.entity(id).unwrap();
+    assert!(app.world.get::<SaveGroup<Profile>>(entity).is_some());
+}
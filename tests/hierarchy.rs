@@ -0,0 +1,168 @@
+use std::path::PathBuf;
+
+use bevy::ecs::entity::{EntityMap, MapEntities, MapEntitiesError};
+use bevy::ecs::reflect::ReflectMapEntities;
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Reflect)]
+#[reflect(Component, MapEntities)]
+struct Target(Entity);
+
+impl Default for Target {
+    fn default() -> Self {
+        Self(Entity::from_raw(u32::MAX))
+    }
+}
+
+impl MapEntities for Target {
+    fn map_entities(&mut self, entity_map: &EntityMap) -> Result<(), MapEntitiesError> {
+        self.0 = entity_map.get(self.0)?;
+        Ok(())
+    }
+}
+
+fn app(plugin: SavePlugin) -> App {
+    let mut app = test_app(plugin);
+    app.add_plugin(HierarchyPlugin).register_type::<Target>();
+    app
+}
+
+#[test]
+fn load_hierarchy() {
+    let dir = test_dir("load_hierarchy");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app(SavePlugin::default());
+        // Recycle some indices, so saved entities have non-zero generations:
+        for _ in 0..2 {
+            let entities: Vec<Entity> = (0..4).map(|_| app.world.spawn_empty().id()).collect();
+            entities.into_iter().for_each(|entity| {
+                app.world.despawn(entity);
+            });
+        }
+        let child = app.world.spawn(Save).id();
+        let parent = app.world.spawn(Save).push_children(&[child]).id();
+        app.world.spawn((Save, Target(child)));
+        assert_ne!(child.generation(), 0);
+        assert_ne!(parent.generation(), 0);
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app(SavePlugin::default());
+    for _ in 0..8 {
+        app.world.spawn_empty();
+    }
+    app.world.load(&path);
+    app.update();
+
+    let (parent, children) = app.world.query::<(Entity, &Children)>().single(&app.world);
+    let child = children[0];
+    assert_eq!(children.len(), 1);
+    assert_eq!(app.world.get::<Parent>(child).unwrap().get(), parent);
+    let target = app.world.query::<&Target>().single(&app.world);
+    assert_eq!(target.0, child);
+}
+
+fn save_hierarchy_with_unsaved_child(path: &PathBuf) {
+    let mut app = app(SavePlugin::default());
+    let unsaved = app.world.spawn_empty().id();
+    let child = app.world.spawn(Save).id();
+    app.world.spawn(Save).push_children(&[unsaved, child]);
+    for _ in 0..4 {
+        let child = app.world.spawn(Save).id();
+        app.world.spawn(Save).push_children(&[child]);
+        app.world.spawn((Save, Target(child)));
+    }
+    app.world.save(path);
+    app.update();
+}
+
+fn load_hierarchy_with_unsaved_child(path: &PathBuf, policy: DanglingPolicy) -> App {
+    let mut app = app(SavePlugin::default().with_dangling_policy(policy));
+    // Offset loaded entities, so unmapped references do not point at them by coincidence:
+    for _ in 0..16 {
+        app.world.spawn_empty();
+    }
+    app.world.load(path);
+    app.update();
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+    assert!(!app.world.resource::<Events<LoadedEvent>>().is_empty());
+    app
+}
+
+fn loaded_entities(app: &mut App) -> Vec<Entity> {
+    app.world
+        .query_filtered::<Entity, With<Save>>()
+        .iter(&app.world)
+        .collect()
+}
+
+#[test]
+fn load_hierarchy_with_placeholder_child() {
+    let dir = test_dir("load_hierarchy_with_placeholder_child");
+    let path = dir.join("world.ron");
+    save_hierarchy_with_unsaved_child(&path);
+
+    let mut app = load_hierarchy_with_unsaved_child(&path, DanglingPolicy::Placeholder);
+
+    // The unsaved child is replaced with a placeholder, while all other children are remapped:
+    let loaded = loaded_entities(&mut app);
+    let mut placeholders = 0;
+    for (parent, children) in app.world.query::<(Entity, &Children)>().iter(&app.world) {
+        for &child in children.iter() {
+            if child == PLACEHOLDER_ENTITY {
+                placeholders += 1;
+            } else {
+                assert!(loaded.contains(&child));
+                assert_eq!(app.world.get::<Parent>(child).unwrap().get(), parent);
+            }
+        }
+    }
+    assert_eq!(placeholders, 1);
+    for parent in app.world.query::<&Parent>().iter(&app.world) {
+        assert!(loaded.contains(&parent.get()));
+    }
+    for target in app.world.query::<&Target>().iter(&app.world) {
+        assert!(loaded.contains(&target.0));
+    }
+
+    let events: Vec<DanglingEntitiesEvent> = app
+        .world
+        .resource_mut::<Events<DanglingEntitiesEvent>>()
+        .drain()
+        .collect();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].component, std::any::type_name::<Children>());
+    assert_eq!(events[0].dangling.0.len(), 1);
+}
+
+#[test]
+fn load_hierarchy_with_removed_children() {
+    let dir = test_dir("load_hierarchy_with_removed_children");
+    let path = dir.join("world.ron");
+    save_hierarchy_with_unsaved_child(&path);
+
+    let mut app = load_hierarchy_with_unsaved_child(&path, DanglingPolicy::Remove);
+
+    let loaded = loaded_entities(&mut app);
+    assert_eq!(app.world.query::<&Children>().iter(&app.world).count(), 4);
+    for parent in app.world.query::<&Parent>().iter(&app.world) {
+        assert!(loaded.contains(&parent.get()));
+    }
+}
+
+#[test]
+#[should_panic]
+fn load_hierarchy_with_unsaved_child_panics() {
+    let dir = test_dir("load_hierarchy_with_unsaved_child_panics");
+    let path = dir.join("world.ron");
+    save_hierarchy_with_unsaved_child(&path);
+
+    load_hierarchy_with_unsaved_child(&path, DanglingPolicy::Panic);
+}
@@ -0,0 +1,103 @@
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct QuestTarget(SaveRef);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<QuestTarget>();
+    app
+}
+
+#[test]
+fn save_id_assigned() {
+    let mut app = app();
+    let a = app.world.spawn(Save).id();
+    let b = app.world.spawn(Save).id();
+    let unsaved = app.world.spawn_empty().id();
+    app.update();
+
+    let index = app.world.resource::<SaveIdIndex>();
+    let id = |entity| *app.world.get::<SaveId>(entity).unwrap();
+    assert_ne!(id(a), id(b));
+    assert_eq!(index.entity(id(a)), Some(a));
+    assert_eq!(index.id(b), Some(id(b)));
+    assert!(app.world.get::<SaveId>(unsaved).is_none());
+
+    app.world.despawn(a);
+    app.update();
+    let index = app.world.resource::<SaveIdIndex>();
+    assert_eq!(index.len(), 1);
+}
+
+#[test]
+fn save_id_preserved() {
+    let dir = test_dir("save_id_preserved");
+    let path = dir.join("world.ron");
+
+    let (target_id, quest_id) = {
+        let mut app = app();
+        let target = app.world.spawn(Save).id();
+        app.update();
+        let index = app.world.resource::<SaveIdIndex>();
+        let target_ref = index.save_ref(target).unwrap();
+        let quest = app.world.spawn((Save, QuestTarget(target_ref))).id();
+        app.world.save(&path);
+        app.update();
+        let quest_id = *app.world.get::<SaveId>(quest).unwrap();
+        (target_ref.id().unwrap(), quest_id)
+    };
+
+    let mut app = app();
+    for _ in 0..4 {
+        app.world.spawn_empty();
+    }
+    app.world.load(&path);
+    app.update();
+
+    let index = app.world.resource::<SaveIdIndex>();
+    let target = index.entity(target_id).unwrap();
+    let quest = index.entity(quest_id).unwrap();
+    let QuestTarget(target_ref) = app.world.get::<QuestTarget>(quest).unwrap();
+    assert_eq!(target_ref.entity(index), Some(target));
+    assert_eq!(app.world.get::<SaveId>(target), Some(&target_id));
+
+    // New IDs must not collide with loaded IDs:
+    let entity = app.world.spawn(Save).id();
+    app.update();
+    let id = *app.world.get::<SaveId>(entity).unwrap();
+    assert_ne!(id, target_id);
+    assert_ne!(id, quest_id);
+}
+
+#[test]
+fn save_id_not_reused() {
+    let dir = test_dir("save_id_not_reused");
+    let path = dir.join("world.ron");
+
+    let despawned_id = {
+        let mut app = app();
+        app.world.spawn(Save);
+        let despawned = app.world.spawn(Save).id();
+        app.update();
+        let despawned_id = *app.world.get::<SaveId>(despawned).unwrap();
+        app.world.despawn(despawned);
+        app.world.save(&path);
+        app.update();
+        despawned_id
+    };
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+
+    let entity = app.world.spawn(Save).id();
+    app.update();
+    let id = *app.world.get::<SaveId>(entity).unwrap();
+    assert!(id.get() > despawned_id.get());
+}
@@ -0,0 +1,276 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+fn app() -> App {
+    test_app(SavePlugin::default())
+}
+
+#[test]
+fn load_sends_event() {
+    let dir = test_dir("load_sends_event");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.world.spawn(Save);
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    let id = app.world.load(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(event.id, id);
+    assert_eq!(event.path, path);
+    assert_eq!(event.mode, LoadMode::Replace);
+}
+
+#[test]
+fn load_missing_file_sends_failed_event() {
+    let dir = test_dir("load_missing_file_sends_failed_event");
+    let path = dir.join("world.ron");
+
+    let mut app = app();
+    let id = app.world.load_additive(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(event.id, id);
+    assert_eq!(event.path, path);
+    assert_eq!(event.mode, LoadMode::Additive);
+    assert!(matches!(event.error, LoadError::Io(_)));
+}
+
+#[test]
+fn load_invalid_file_sends_failed_event() {
+    let dir = test_dir("load_invalid_file_sends_failed_event");
+    let path = dir.join("world.ron");
+    fs::write(&path, "(entities: {").unwrap();
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(matches!(event.error, LoadError::Deserialize(_)));
+}
+
+#[derive(Default, Reflect)]
+struct NotComponent;
+
+#[test]
+fn load_failure_preserves_world() {
+    let dir = test_dir("load_failure_preserves_world");
+    let path = dir.join("world.ron");
+    // `NotComponent` is registered, so deserialization succeeds, but the world write must fail:
+    fs::write(
+        &path,
+        r#"(entities: { 0: (components: { "load::NotComponent": () }) })"#,
+    )
+    .unwrap();
+
+    let mut app = app();
+    app.register_type::<NotComponent>();
+    let entity = app.world.spawn(Save).id();
+    app.world.load(&path);
+    app.update();
+
+    assert!(app.world.get_entity(entity).is_some());
+    assert!(app.world.resource::<RequestQueue>().is_empty());
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(matches!(event.error, LoadError::WorldWrite(_)));
+
+    // Subsequent updates must not attempt to load again:
+    app.update();
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+}
+
+#[test]
+fn load_corrupted_file_sends_failed_event() {
+    let dir = test_dir("load_corrupted_file_sends_failed_event");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.world.spawn(Save);
+        app.world.save(&path);
+        app.update();
+    }
+
+    // Trailing whitespace is still valid RON, so only the checksum may detect this:
+    let mut bytes = fs::read(&path).unwrap();
+    bytes.push(b' ');
+    fs::write(&path, bytes).unwrap();
+
+    let mut app = app();
+    let entity = app.world.spawn(Save).id();
+    app.world.load(&path);
+    app.update();
+
+    assert!(app.world.get_entity(entity).is_some());
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(matches!(event.error, LoadError::Corrupted { .. }));
+}
+
+/// Saves a world, damages the saved file using `damage`, and returns the [`LoadError`] of loading it.
+fn load_damaged(name: &str, damage: impl FnOnce(&mut Vec<u8>)) -> LoadError {
+    let dir = test_dir(name);
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.world.spawn(Save);
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut bytes = fs::read(&path).unwrap();
+    damage(&mut bytes);
+    fs::write(&path, bytes).unwrap();
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    event.error
+}
+
+/// Returns the offset of the first occurrence of `pattern` in `bytes`.
+fn find(bytes: &[u8], pattern: &[u8]) -> usize {
+    bytes
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+        .unwrap()
+}
+
+#[test]
+fn load_damaged_header_magic() {
+    let error = load_damaged("load_damaged_header_magic", |bytes| bytes[5] ^= 0x01);
+    assert!(matches!(error, LoadError::Corrupted { .. }));
+}
+
+#[test]
+fn load_damaged_header_checksum() {
+    let error = load_damaged("load_damaged_header_checksum", |bytes| {
+        let offset = find(bytes, b"bevy_atomic_save ") + "bevy_atomic_save ".len();
+        bytes[offset] = b'x';
+    });
+    assert!(matches!(error, LoadError::Corrupted { expected: None, .. }));
+}
+
+#[test]
+fn load_damaged_header_fields() {
+    let error = load_damaged("load_damaged_header_version", |bytes| {
+        let offset = find(bytes, b"version:") + "version:".len();
+        bytes[offset] ^= 0x01;
+    });
+    assert!(matches!(
+        error,
+        LoadError::Corrupted {
+            expected: Some(_),
+            ..
+        }
+    ));
+
+    let error = load_damaged("load_damaged_header_format", |bytes| {
+        let offset = find(bytes, b"format:") + "format:".len();
+        bytes[offset] ^= 0x01;
+    });
+    assert!(matches!(
+        error,
+        LoadError::Corrupted {
+            expected: Some(_),
+            ..
+        }
+    ));
+}
+
+#[test]
+fn load_without_header() {
+    let dir = test_dir("load_without_header");
+    let path = dir.join("world.ron");
+    fs::write(&path, "(entities: {})").unwrap();
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+    assert!(!app.world.resource::<Events<LoadedEvent>>().is_empty());
+}
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Marker;
+
+#[test]
+fn load_in_background() {
+    let dir = test_dir("load_in_background");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.register_type::<Marker>();
+        app.world.spawn((Save, Marker));
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    app.register_type::<Marker>();
+    let entity = app.world.spawn(Save).id();
+    app.world.load_in_background(&path);
+    app.update();
+
+    // The world must not be touched until the saved world is ready:
+    assert!(app.world.get_entity(entity).is_some());
+    assert_eq!(app.world.resource::<LoadProgress>().path(), path);
+
+    let mut loaded = false;
+    while !loaded {
+        app.update();
+        loaded = !app.world.resource::<Events<LoadedEvent>>().is_empty();
+    }
+    assert!(app.world.get_entity(entity).is_none());
+    assert!(!app.world.contains_resource::<LoadProgress>());
+    assert!(app.world.resource::<RequestQueue>().is_empty());
+    let mut query = app.world.query_filtered::<(), With<Marker>>();
+    assert_eq!(query.iter(&app.world).count(), 1);
+}
@@ -0,0 +1,74 @@
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, FromLoaded)]
+#[reflect(Component, FromLoaded)]
+struct Targets(Vec<Entity>);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default().with_dangling_policy(DanglingPolicy::Placeholder));
+    app.register_type::<Targets>()
+        .register_type::<Vec<Entity>>();
+    app
+}
+
+#[test]
+fn loaded_stale_reference() {
+    let dir = test_dir("loaded_stale_reference");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        let stale = app.world.spawn_empty().id();
+        app.world.despawn(stale);
+        // The index of the stale entity is reused:
+        let entity = app.world.spawn(Save).id();
+        assert_eq!(entity.index(), stale.index());
+        app.world.spawn((Save, Targets(vec![entity, stale])));
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    app.world.load(&path);
+    app.update();
+
+    let targets = app.world.query::<&Targets>().single(&app.world);
+    assert!(app.world.get_entity(targets.0[0]).is_some());
+    assert_eq!(targets.0[1], PLACEHOLDER_ENTITY);
+}
+
+#[test]
+fn loaded_iter() {
+    let dir = test_dir("loaded_iter");
+    let path = dir.join("world.ron");
+
+    let saved = {
+        let mut app = app();
+        let stale = app.world.spawn_empty().id();
+        app.world.despawn(stale);
+        let saved = [app.world.spawn(Save).id(), app.world.spawn(Save).id()];
+        app.world.save(&path);
+        app.update();
+        saved
+    };
+
+    let mut app = app();
+    for _ in 0..4 {
+        app.world.spawn_empty();
+    }
+    app.world.load(&path);
+    app.add_system_to_stage(SaveStage::PostLoad, move |loaded: Res<Loaded>| {
+        assert_eq!(loaded.len(), 2);
+        for (saved_entity, entity) in loaded.iter() {
+            assert!(saved.contains(&saved_entity));
+            assert_eq!(loaded.entity(saved_entity), Some(entity));
+            assert_eq!(loaded.saved_entity(entity), Some(saved_entity));
+        }
+        assert_eq!(loaded.entity(Entity::from_raw(saved[0].index())), None);
+    });
+    app.update();
+}
@@ -0,0 +1,74 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Resource, Default, Reflect, FromReflect, PartialEq, Debug, Clone)]
+struct SaveInfo {
+    level: String,
+    play_time: f32,
+}
+
+fn app() -> App {
+    test_app(SavePlugin::default())
+}
+
+#[test]
+fn metadata_from_resource() {
+    let dir = test_dir("metadata_from_resource");
+    let path = dir.join("world.ron");
+    let info = SaveInfo {
+        level: "Level \"1\"\nDungeon".to_string(),
+        play_time: 42.0,
+    };
+
+    let mut app = app();
+    app.insert_resource(info.clone())
+        .register_save_metadata::<SaveInfo>();
+    app.world.save(&path);
+    app.update();
+
+    let metadata = read_save_metadata(&path).unwrap();
+    assert!(metadata.timestamp.is_some());
+    let registry = app.world.resource::<AppTypeRegistry>();
+    assert_eq!(metadata.get::<SaveInfo>(registry), Some(info));
+
+    // The world must still load normally:
+    app.world.load(&path);
+    app.update();
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+}
+
+#[test]
+fn metadata_from_callback() {
+    let dir = test_dir("metadata_from_callback");
+    let path = dir.join("world.ron");
+
+    let mut app = app();
+    app.register_save_metadata_with(|world| SaveInfo {
+        level: "callback".to_string(),
+        play_time: world.entities().len() as f32,
+    });
+    app.world.spawn(Save);
+    app.world.save(&path);
+    app.update();
+
+    let metadata = read_save_metadata(&path).unwrap();
+    let registry = app.world.resource::<AppTypeRegistry>();
+    let info = metadata.get::<SaveInfo>(registry).unwrap();
+    assert_eq!(info.level, "callback");
+    assert_eq!(info.play_time, 1.0);
+}
+
+#[test]
+fn metadata_without_header() {
+    let dir = test_dir("metadata_without_header");
+    let path = dir.join("world.ron");
+    fs::write(&path, "(entities: {})").unwrap();
+
+    let metadata = read_save_metadata(&path).unwrap();
+    assert_eq!(metadata, SaveMetadata::default());
+}
@@ -0,0 +1,204 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect, FromReflect)]
+#[reflect(Component)]
+struct OldHealth(u32);
+
+#[derive(Component, Default, Reflect, PartialEq, Debug)]
+#[reflect(Component)]
+struct Health(f32);
+
+mod legacy {
+    use bevy::prelude::*;
+
+    /// Layout of [`super::Stamina`] in version `0`.
+    #[derive(Default, Reflect, FromReflect)]
+    pub struct Stamina {
+        pub points: u32,
+    }
+}
+
+#[derive(Component, Default, Reflect, PartialEq, Debug)]
+#[reflect(Component)]
+struct Stamina {
+    current: u32,
+}
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<OldHealth>().register_type::<Health>();
+    app
+}
+
+fn migrate_health(app: &mut App) {
+    app.set_save_version(1).register_migration(0, |saved| {
+        for component in saved.components_mut() {
+            if let Some(OldHealth(health)) = OldHealth::from_reflect(&**component) {
+                *component = Box::new(Health(health as f32));
+            }
+        }
+        Ok(())
+    });
+}
+
+fn load_failed_event(app: &mut App) -> LoadFailedEvent {
+    app.world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap()
+}
+
+#[test]
+fn migrate_legacy_save() {
+    let dir = test_dir("migrate_legacy_save");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.world.spawn((Save, OldHealth(10)));
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    migrate_health(&mut app);
+    app.world.load(&path);
+    app.update();
+
+    let mut query = app.world.query::<&Health>();
+    assert_eq!(query.single(&app.world), &Health(10.0));
+    assert!(app
+        .world
+        .query::<&OldHealth>()
+        .iter(&app.world)
+        .next()
+        .is_none());
+
+    // Saves are stamped with the current version, so they are not migrated again:
+    app.world.save(&path);
+    app.update();
+    app.world.load(&path);
+    app.update();
+    let mut query = app.world.query::<&Health>();
+    assert_eq!(query.single(&app.world), &Health(10.0));
+}
+
+#[test]
+fn newer_version() {
+    let dir = test_dir("newer_version");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.set_save_version(2);
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    migrate_health(&mut app);
+    app.world.load(&path);
+    app.update();
+
+    let event = load_failed_event(&mut app);
+    assert!(matches!(event.error, LoadError::UnsupportedVersion(2)));
+}
+
+#[test]
+fn missing_migration() {
+    let dir = test_dir("missing_migration");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    migrate_health(&mut app);
+    app.set_save_version(2);
+    app.world.load(&path);
+    app.update();
+
+    let event = load_failed_event(&mut app);
+    assert!(matches!(event.error, LoadError::UnsupportedVersion(1)));
+}
+
+#[test]
+fn failed_migration() {
+    let dir = test_dir("failed_migration");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    app.set_save_version(1)
+        .register_migration(0, |_| Err("invalid save".into()));
+    let entity = app.world.spawn(Save).id();
+    app.world.load(&path);
+    app.update();
+
+    assert!(app.world.get_entity(entity).is_some());
+    let event = load_failed_event(&mut app);
+    assert!(matches!(
+        event.error,
+        LoadError::Migration { version: 0, .. }
+    ));
+}
+
+#[test]
+fn migrate_changed_layout() {
+    let dir = test_dir("migrate_changed_layout");
+    let path = dir.join("world.ron");
+
+    // Save files without a header have version 0:
+    let type_name = std::any::type_name::<Stamina>();
+    fs::write(
+        &path,
+        format!(
+            r#"(resources: {{}}, entities: {{0: (components: {{"{type_name}": (points: 10)}})}})"#
+        ),
+    )
+    .unwrap();
+
+    let mut app = app();
+    app.register_type::<Stamina>()
+        .set_save_version(1)
+        .register_legacy_type::<legacy::Stamina>(0, type_name)
+        .register_migration(0, |saved| {
+            for component in saved.components_mut() {
+                if let Some(legacy::Stamina { points }) =
+                    legacy::Stamina::from_reflect(&**component)
+                {
+                    *component = Box::new(Stamina { current: points });
+                }
+            }
+            Ok(())
+        });
+    app.world.load(&path);
+    app.update();
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+    let mut query = app.world.query::<&Stamina>();
+    assert_eq!(query.single(&app.world), &Stamina { current: 10 });
+
+    // Current save files use the current layout:
+    app.world.save(&path);
+    app.update();
+    app.world.load(&path);
+    app.update();
+    assert!(app.world.resource::<Events<LoadFailedEvent>>().is_empty());
+    let mut query = app.world.query::<&Stamina>();
+    assert_eq!(query.single(&app.world), &Stamina { current: 10 });
+}
@@ -0,0 +1,112 @@
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Marker(u32);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Marker>();
+    app
+}
+
+fn markers(app: &mut App) -> Vec<u32> {
+    let mut query = app.world.query::<&Marker>();
+    query.iter(&app.world).map(|Marker(value)| *value).collect()
+}
+
+#[test]
+fn multiple_saves() {
+    let dir = test_dir("multiple_saves");
+    let a = dir.join("a.ron");
+    let b = dir.join("b.ron");
+
+    let mut app = app();
+    app.world.spawn((Save, Marker(0)));
+    app.world.save(&a);
+    app.world.save(&b);
+    assert_eq!(app.world.resource::<RequestQueue>().len(), 2);
+    app.update();
+
+    assert!(a.exists());
+    assert!(b.exists());
+    assert!(app.world.resource::<RequestQueue>().is_empty());
+}
+
+#[test]
+fn save_before_load() {
+    let dir = test_dir("save_before_load");
+    let a = dir.join("a.ron");
+    let b = dir.join("b.ron");
+
+    {
+        let mut app = app();
+        app.world.spawn((Save, Marker(1)));
+        app.world.save(&a);
+        app.update();
+
+        app.world.spawn((Save, Marker(2)));
+        let (load_path, save_path) = (a.clone(), b.clone());
+        app.add_system(move |mut commands: Commands, mut done: Local<bool>| {
+            if !*done {
+                commands.load(&load_path);
+                commands.save(&save_path);
+                *done = true;
+            }
+        });
+        app.update();
+        app.update();
+
+        // `b` is saved before `a` is loaded:
+        assert_eq!(markers(&mut app), vec![1]);
+    }
+
+    let mut app = app();
+    app.world.load(&b);
+    app.update();
+    let mut values = markers(&mut app);
+    values.sort();
+    assert_eq!(values, vec![1, 2]);
+}
+
+#[test]
+fn cancel_request() {
+    let dir = test_dir("cancel_request");
+    let a = dir.join("a.ron");
+    let b = dir.join("b.ron");
+
+    let mut app = app();
+    app.world.spawn((Save, Marker(0)));
+    let id = app.world.save(&a);
+    app.world.save(&b);
+    let request = app.world.resource_mut::<RequestQueue>().cancel(id).unwrap();
+    assert_eq!(request.path(), a);
+    app.update();
+
+    assert!(!a.exists());
+    assert!(b.exists());
+}
+
+#[test]
+fn cancel_background_load() {
+    let dir = test_dir("cancel_background_load");
+    let a = dir.join("a.ron");
+
+    let mut app = app();
+    app.world.spawn((Save, Marker(0)));
+    app.world.save(&a);
+    app.update();
+
+    app.world.load_in_background(&a);
+    app.update();
+    assert!(app.world.contains_resource::<LoadProgress>());
+
+    app.world.resource_mut::<RequestQueue>().clear();
+    app.update();
+    assert!(!app.world.contains_resource::<LoadProgress>());
+    assert!(app.world.resource::<Events<LoadedEvent>>().is_empty());
+}
@@ -0,0 +1,126 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+struct Clock {
+    time: f64,
+    #[reflect(ignore)]
+    ticks: u32,
+}
+
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+struct Seed(u64);
+
+fn app() -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_saved_resource::<Clock>()
+        .register_saved_resource_with::<Seed>(LoadResourceMode::Apply);
+    app
+}
+
+#[test]
+fn resources_round_trip() {
+    let dir = test_dir("resources_round_trip");
+    let path = dir.join("world.ron");
+
+    {
+        let mut app = app();
+        app.insert_resource(Clock {
+            time: 42.0,
+            ticks: 7,
+        })
+        .insert_resource(Seed(1234));
+        app.world.save(&path);
+        app.update();
+    }
+
+    let mut app = app();
+    app.insert_resource(Clock {
+        time: 1.0,
+        ticks: 3,
+    });
+    app.world.load(&path);
+    app.update();
+
+    // `Clock` is replaced, so its ignored fields are reset:
+    let clock = app.world.resource::<Clock>();
+    assert_eq!(clock.time, 42.0);
+    assert_eq!(clock.ticks, 0);
+    // `Seed` did not exist, so it is inserted:
+    assert_eq!(app.world.resource::<Seed>().0, 1234);
+}
+
+#[test]
+fn resources_apply() {
+    let dir = test_dir("resources_apply");
+    let path = dir.join("world.ron");
+    fs::write(
+        &path,
+        r#"(resources: { "resource::Seed": (1234) }, entities: {})"#,
+    )
+    .unwrap();
+
+    let mut app = app();
+    app.insert_resource(Seed(1));
+    app.world.load(&path);
+    app.update();
+
+    assert_eq!(app.world.resource::<Seed>().0, 1234);
+}
+
+#[test]
+fn load_without_resources() {
+    let dir = test_dir("load_without_resources");
+    let path = dir.join("world.ron");
+    fs::write(&path, r#"(entities: {})"#).unwrap();
+
+    let mut app = app();
+    app.insert_resource(Seed(1));
+    app.world.load(&path);
+    app.update();
+
+    assert_eq!(app.world.resource::<Seed>().0, 1);
+    let events = app.world.resource::<Events<LoadedEvent>>();
+    assert!(!events.is_empty());
+}
+
+#[derive(Resource, Default, Reflect)]
+#[reflect(Resource)]
+struct Difficulty(u32);
+
+#[test]
+fn load_unsaved_resource_fails() {
+    let dir = test_dir("load_unsaved_resource_fails");
+    let path = dir.join("world.ron");
+    fs::write(
+        &path,
+        r#"(resources: { "resource::Difficulty": (9) }, entities: {})"#,
+    )
+    .unwrap();
+
+    let mut app = app();
+    // Reflected, but not registered as a saved resource:
+    app.register_type::<Difficulty>()
+        .insert_resource(Difficulty(1));
+    app.world.load(&path);
+    app.update();
+
+    assert_eq!(app.world.resource::<Difficulty>().0, 1);
+    let event = app
+        .world
+        .resource_mut::<Events<LoadFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(matches!(
+        event.error,
+        LoadError::UnregisteredResource { .. }
+    ));
+}
@@ -0,0 +1,171 @@
+use std::fs;
+use std::io::{self, Write};
+
+use bevy::app::AppExit;
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn write_atomic_replaces_file() {
+    let dir = test_dir("write_atomic_replaces_file");
+    let path = dir.join("world.ron");
+    fs::write(&path, "old").unwrap();
+
+    write_atomic(&path, |file| file.write_all(b"new")).unwrap();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "new");
+    assert!(!dir.join("world.ron.tmp").exists());
+}
+
+#[test]
+fn write_atomic_failure_preserves_file() {
+    let dir = test_dir("write_atomic_failure_preserves_file");
+    let path = dir.join("world.ron");
+    fs::write(&path, "old").unwrap();
+
+    let result = write_atomic(&path, |file| {
+        file.write_all(b"partial")?;
+        Err(io::Error::other("simulated failure"))
+    });
+
+    assert!(result.is_err());
+    assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+    assert!(!dir.join("world.ron.tmp").exists());
+}
+
+#[test]
+fn save_failure_preserves_file() {
+    let dir = test_dir("save_failure_preserves_file");
+    let path = dir.join("world.ron");
+    fs::write(&path, "old").unwrap();
+
+    // A directory in place of the temporary file causes the write to fail.
+    fs::create_dir(dir.join("world.ron.tmp")).unwrap();
+
+    let mut app = test_app(SavePlugin::default());
+    app.world.spawn(Save);
+    app.world.save(&path);
+    app.update();
+
+    assert_eq!(fs::read_to_string(&path).unwrap(), "old");
+    let event = app
+        .world
+        .resource_mut::<Events<SaveFailedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(event.path, path);
+    assert!(matches!(event.error, SaveError::Io(_)));
+}
+
+#[test]
+fn save_sends_event() {
+    let dir = test_dir("save_sends_event");
+    let path = dir.join("world.ron");
+
+    let mut app = test_app(SavePlugin::default());
+    app.world.spawn(Save);
+    let id = app.world.save(&path);
+    app.update();
+
+    assert!(path.exists());
+    let event = app
+        .world
+        .resource_mut::<Events<SavedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(event.id, id);
+    assert_eq!(event.path, path);
+    assert_eq!(event.mode, SaveMode::Filtered);
+}
+
+#[test]
+fn save_skips_unchanged() {
+    let dir = test_dir("save_skips_unchanged");
+    let path = dir.join("world.ron");
+    let next_path = dir.join("next.ron");
+
+    let mut app = test_app(SavePlugin::default());
+    app.world.spawn(Save);
+    app.world.save(&path);
+    app.update();
+    let saved = app
+        .world
+        .resource_mut::<Events<SavedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(!saved.skipped);
+
+    app.world
+        .queue_request(Request::save(&next_path).skip_if_unchanged(saved.checksum));
+    app.update();
+    let event = app
+        .world
+        .resource_mut::<Events<SavedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert!(event.skipped);
+    assert_eq!(event.checksum, saved.checksum);
+    assert!(!next_path.exists());
+}
+
+#[test]
+fn save_in_background() {
+    let dir = test_dir("save_in_background");
+    let path = dir.join("world.ron");
+
+    let mut app = test_app(SavePlugin::default());
+    app.world.spawn(Save);
+    app.world.save_in_background(&path);
+    app.update();
+
+    let mut saved = app
+        .world
+        .resource_mut::<Events<SavedEvent>>()
+        .drain()
+        .count();
+
+    // Saving into the same file again must wait for the pending save:
+    app.world.save_in_background(&path);
+    app.update();
+    saved += app
+        .world
+        .resource_mut::<Events<SavedEvent>>()
+        .drain()
+        .count();
+    assert_eq!(saved, 1);
+
+    while !app.world.resource::<PendingSaves>().is_empty() {
+        app.update();
+        saved += app
+            .world
+            .resource_mut::<Events<SavedEvent>>()
+            .drain()
+            .count();
+    }
+    assert_eq!(saved, 2);
+    assert!(path.exists());
+    assert!(!dir.join("world.ron.tmp").exists());
+}
+
+#[test]
+fn save_in_background_on_exit() {
+    let dir = test_dir("save_in_background_on_exit");
+    let path = dir.join("world.ron");
+
+    let mut app = test_app(SavePlugin::default());
+    app.world.spawn(Save);
+    app.world.save_in_background(&path);
+    app.world.send_event(AppExit);
+    app.update();
+
+    assert!(app.world.resource::<PendingSaves>().is_empty());
+    assert!(!app.world.resource::<Events<SavedEvent>>().is_empty());
+    assert!(path.exists());
+}
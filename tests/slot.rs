@@ -0,0 +1,137 @@
+use std::fs;
+
+use bevy::prelude::*;
+use bevy_atomic_save::*;
+
+mod common;
+use common::*;
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+struct Marker;
+
+fn app(slots: SaveSlots) -> App {
+    let mut app = test_app(SavePlugin::default());
+    app.register_type::<Marker>().insert_resource(slots);
+    app
+}
+
+#[test]
+fn save_and_load_slot() {
+    let dir = test_dir("save_and_load_slot").join("saves");
+    let slots = SaveSlots::new(&dir);
+
+    let mut app = app(slots.clone());
+    app.world.spawn((Save, Marker));
+    app.add_startup_system(|mut commands: Commands| {
+        commands.save_slot("1");
+    });
+    app.update();
+    assert!(slots.exists("1"));
+    assert!(app.world.resource::<Events<SaveFailedEvent>>().is_empty());
+
+    app.world.spawn((Save, Marker));
+    app.world.load_slot("1");
+    app.update();
+
+    let event = app
+        .world
+        .resource_mut::<Events<LoadedEvent>>()
+        .drain()
+        .next()
+        .unwrap();
+    assert_eq!(event.path, slots.path("1").unwrap());
+    let mut query = app.world.query::<&Marker>();
+    assert_eq!(query.iter(&app.world).count(), 1);
+}
+
+#[test]
+fn manage_slots() {
+    let dir = test_dir("manage_slots");
+    let slots = SaveSlots::new(&dir);
+    assert!(slots.list().unwrap().is_empty());
+
+    let mut app = app(slots.clone());
+    app.world.save_slot("b");
+    app.world.save_slot("a");
+    app.update();
+    fs::write(dir.join("notes.txt"), "not a slot").unwrap();
+
+    let names = |slots: &SaveSlots| -> Vec<String> {
+        slots
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|slot| slot.name)
+            .collect()
+    };
+    assert_eq!(names(&slots), ["a", "b"]);
+    assert!(slots.list().unwrap()[0].metadata.is_ok());
+
+    slots.copy("a", "c").unwrap();
+    assert_eq!(
+        fs::read(slots.path("a").unwrap()).unwrap(),
+        fs::read(slots.path("c").unwrap()).unwrap()
+    );
+    slots.rename("b", "d").unwrap();
+    slots.delete("a").unwrap();
+    assert_eq!(names(&slots), ["c", "d"]);
+}
+
+#[test]
+fn invalid_slot_names() {
+    for name in ["", ".", "..", "../a", "a/b", "a\\b", "/a", "a/"] {
+        assert!(!SaveSlots::is_valid_name(name), "{name:?}");
+    }
+    for name in ["a", "a.b", "..a", "quick save"] {
+        assert!(SaveSlots::is_valid_name(name), "{name:?}");
+    }
+
+    let dir = test_dir("invalid_slot_names");
+    let slots = SaveSlots::new(dir.join("saves"));
+    assert!(slots.path("../a").is_err());
+    assert!(slots.rename("..", "a").is_err());
+
+    let mut app = app(slots);
+    let save = app.world.save_slot("../a");
+    let load = app.world.load_slot("../a");
+    app.update();
+
+    let mut saved = app.world.resource_mut::<Events<SaveFailedEvent>>();
+    assert_eq!(saved.drain().next().unwrap().id, save);
+    let mut loaded = app.world.resource_mut::<Events<LoadFailedEvent>>();
+    assert_eq!(loaded.drain().next().unwrap().id, load);
+    assert!(!dir.join("a.sav").exists());
+}
+
+#[test]
+fn manage_slot_backups() {
+    let dir = test_dir("manage_slot_backups");
+    let slots = SaveSlots::new(&dir);
+    let backups = BackupPolicy::Numbered(2);
+
+    let mut app = test_app(SavePlugin::default().with_backups(backups));
+    app.insert_resource(slots.clone());
+    for _ in 0..3 {
+        app.world.save_slot("a");
+        app.update();
+    }
+    app.world.save_slot("b");
+    app.update();
+    app.world.save_slot("b");
+    app.update();
+    assert_eq!(backups.backups(slots.path("a").unwrap()).len(), 2);
+    assert_eq!(backups.backups(slots.path("b").unwrap()).len(), 1);
+
+    slots.rename("a", "a").unwrap();
+    assert!(slots.exists("a"));
+    assert_eq!(backups.backups(slots.path("a").unwrap()).len(), 2);
+
+    slots.rename("a", "b").unwrap();
+    assert!(backups.backups(slots.path("a").unwrap()).is_empty());
+    assert_eq!(backups.backups(slots.path("b").unwrap()).len(), 2);
+
+    slots.delete("b").unwrap();
+    assert!(backups.backups(slots.path("b").unwrap()).is_empty());
+    assert!(fs::read_dir(&dir).unwrap().next().is_none());
+}